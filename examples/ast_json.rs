@@ -0,0 +1,34 @@
+// ast_json.rs - Example: parse SQL from stdin and print the ASTs as a JSON array.
+// Shows the library API on its own, without the interactive CLI in main.rs.
+// Run with: echo "SELECT name FROM users;" | cargo run --example ast_json --features serde
+// Exits with status 1 (and the error on stderr) if any statement fails to parse.
+
+use std::io::{self, Read};
+use std::process;
+
+use sql_parser::parser::parse_script;
+
+fn main() {
+    let mut input = String::new();
+    if let Err(e) = io::stdin().read_to_string(&mut input) {
+        eprintln!("Failed to read stdin: {}", e);
+        process::exit(1);
+    }
+
+    // parse_script reports tokenizer errors as well as parse errors.
+    let statements: Vec<_> = match parse_script(&input) {
+        Ok(statements) => statements.into_iter().map(|statement| statement.node).collect(),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+
+    match serde_json::to_string_pretty(&statements) {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to serialize: {}", e);
+            process::exit(1);
+        }
+    }
+}
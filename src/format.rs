@@ -0,0 +1,636 @@
+// format.rs - SQL output for parsed statements and expressions.
+// Statement::format and Expression::format let callers choose how identifiers are quoted and whether
+// keywords are upper- or lowercase (e.g., MySQL backticks or ANSI double quotes); Expression::to_sql is
+// the same renderer with the default options. Parentheses are only added where precedence requires them.
+// The AST's Display impls also use this renderer, in a fixed style that parenthesizes every operation
+// and writes strings in double quotes.
+
+// Import the AST types being formatted.
+use crate::statement::{
+    quote_string, truth_value, Assignment, ConflictAction, Constraint, Cte, Expression, GroupByKind, InsertSource, Limit, OnConflict, OrderByItem, SortOrder, Statement,
+    TableColumn, TableSource, UnaryOperator,
+};
+// Import operator associativity for parenthesizing binary operands.
+use crate::parser::Associativity;
+// Import the tokenizer to check which names can be written without quotes.
+use crate::token::Token;
+use crate::tokenizer::Tokenizer;
+
+// Define QuoteStyle enum for how identifiers (table, column, and alias names) are written.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum QuoteStyle {
+    #[default]
+    None, // Bare identifiers (e.g., name), as Display writes them.
+    DoubleQuote, // ANSI / PostgreSQL (e.g., "name").
+    Backtick, // MySQL (e.g., `name`).
+    Bracket, // SQL Server (e.g., [name]).
+    AsNeeded, // Bare when valid unquoted; otherwise [name], the delimited form the tokenizer reads back.
+}
+
+impl QuoteStyle {
+    // Quote an identifier in this style, doubling the closing quote character if it appears inside.
+    pub fn quote(self, ident: &str) -> String {
+        match self {
+            QuoteStyle::None => ident.to_string(),
+            QuoteStyle::DoubleQuote => format!("\"{}\"", ident.replace('"', "\"\"")),
+            QuoteStyle::Backtick => format!("`{}`", ident.replace('`', "``")),
+            QuoteStyle::Bracket => format!("[{}]", ident.replace(']', "]]")),
+            QuoteStyle::AsNeeded if is_bare_identifier(ident) => ident.to_string(),
+            QuoteStyle::AsNeeded => QuoteStyle::Bracket.quote(ident),
+        }
+    }
+}
+
+// Quote a collation name. The parser reads it as a string token, so besides doubling " the
+// backslashes the tokenizer would treat as escapes are escaped too (e.g., a"b\c -> "a""b\\c").
+fn quote_collation(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\"\""))
+}
+
+// Whether a name can be written without quotes: it tokenizes back to exactly this identifier, so it
+// is not a keyword and contains no spaces or symbols.
+fn is_bare_identifier(name: &str) -> bool {
+    matches!(Tokenizer::new(name).tokenize().as_deref(), Ok([Token::Identifier(ident), Token::Eof]) if ident == name)
+}
+
+// Define FormatOptions struct for Statement::format.
+#[derive(Debug, Clone)]
+pub struct FormatOptions {
+    pub identifier_quote: QuoteStyle, // How table, column, and alias names are quoted.
+    pub uppercase_keywords: bool, // SELECT vs select.
+    pub lowercase_identifiers: bool, // Lowercase names that are valid unquoted (Users -> users); others keep their case.
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            identifier_quote: QuoteStyle::None,
+            uppercase_keywords: true,
+            lowercase_identifiers: false,
+        }
+    }
+}
+
+impl Statement {
+    // Format the statement as SQL using the given options (no trailing semicolon).
+    pub fn format(&self, opts: &FormatOptions) -> String {
+        SqlFormatter { opts, display: false }.statement(self)
+    }
+
+    // Canonical SQL for comparing queries: uppercase keywords and lowercase names. The AST does not
+    // record whether a name was quoted, so only names that could only be written quoted (e.g., [Order Id]
+    // or [Select]) keep their case; those are bracket-quoted so the output parses back to the same AST.
+    pub fn normalized_sql(&self) -> String {
+        self.format(&FormatOptions {
+            identifier_quote: QuoteStyle::AsNeeded,
+            lowercase_identifiers: true,
+            ..FormatOptions::default()
+        })
+    }
+}
+
+impl Expression {
+    // Format the expression as SQL using the given options.
+    pub fn format(&self, opts: &FormatOptions) -> String {
+        SqlFormatter { opts, display: false }.expr(self)
+    }
+}
+
+// Options used by the Display impls: bare identifiers and uppercase keywords.
+static DISPLAY_OPTIONS: FormatOptions = FormatOptions {
+    identifier_quote: QuoteStyle::None,
+    uppercase_keywords: true,
+    lowercase_identifiers: false,
+};
+
+// Walks the AST and builds the SQL text for one set of options.
+pub(crate) struct SqlFormatter<'a> {
+    opts: &'a FormatOptions,
+    display: bool, // Display style: parenthesize every operation and write strings in double quotes.
+}
+
+// Define SqlPart enum for the pending work of SqlFormatter::expr: an expression still to format
+// (and whether to wrap it in parentheses) or literal text to emit.
+enum SqlPart<'a> {
+    Expr(&'a Expression, bool), // Expression to format; true to parenthesize it.
+    Text(String), // Text to append as-is.
+}
+
+impl SqlFormatter<'_> {
+    // The formatter behind the AST's Display impls.
+    pub(crate) fn display() -> SqlFormatter<'static> {
+        SqlFormatter { opts: &DISPLAY_OPTIONS, display: true }
+    }
+
+    // Apply the keyword case. Symbols such as = or + are unaffected by lowercasing.
+    fn kw(&self, keyword: &str) -> String {
+        if self.opts.uppercase_keywords {
+            keyword.to_string()
+        } else {
+            keyword.to_lowercase()
+        }
+    }
+
+    // Apply the name case and the identifier quote style to a table, column, or alias name.
+    fn ident(&self, name: &str) -> String {
+        self.opts.identifier_quote.quote(&self.name_case(name))
+    }
+
+    // Apply lowercase_identifiers to a table, column, alias, or function name. Names that need quotes
+    // keep their case.
+    fn name_case(&self, name: &str) -> String {
+        if self.opts.lowercase_identifiers && is_bare_identifier(name) {
+            name.to_lowercase()
+        } else {
+            name.to_string()
+        }
+    }
+
+    // Function names are left bare so built-ins like COUNT are not turned into quoted identifiers;
+    // each part of a qualified name (math.sqrt) gets the name case on its own.
+    fn function_name(&self, name: &str) -> String {
+        name.split('.').map(|part| self.name_case(part)).collect::<Vec<_>>().join(".")
+    }
+
+    // Format each item and join them with commas.
+    fn list<T>(&self, items: &[T], f: impl Fn(&T) -> String) -> String {
+        items.iter().map(f).collect::<Vec<String>>().join(", ")
+    }
+
+    // Format a whole statement (also used for subqueries).
+    pub(crate) fn statement(&self, statement: &Statement) -> String {
+        match statement {
+            Statement::Select { columns, into, from, r#where, group_by, having, orderby, limit, offset } => format!(
+                "{} {}{} {} {}{}",
+                self.kw("SELECT"),
+                self.list(columns, |c| self.expr(c)),
+                self.into(into),
+                self.kw("FROM"),
+                self.table_source(from),
+                self.select_clauses(r#where, group_by, having, orderby, limit, offset)
+            ),
+            Statement::SelectAll { into, from, r#where, group_by, having, orderby, limit, offset } => format!(
+                "{} *{} {} {}{}",
+                self.kw("SELECT"),
+                self.into(into),
+                self.kw("FROM"),
+                self.table_source(from),
+                self.select_clauses(r#where, group_by, having, orderby, limit, offset)
+            ),
+            Statement::CreateTable { table_name, if_not_exists, column_list } => format!(
+                "{} {}{} ({})",
+                self.kw("CREATE TABLE"),
+                if *if_not_exists { self.kw("IF NOT EXISTS ") } else { String::new() },
+                self.ident(table_name),
+                self.list(column_list, |c| self.column(c))
+            ),
+            Statement::CreateTableAs { name, query } => {
+                format!("{} {} {} {}", self.kw("CREATE TABLE"), self.ident(name), self.kw("AS"), self.statement(query))
+            }
+            Statement::Update { table_name, assignments, r#where, returning } => format!(
+                "{} {} {} {}{}{}",
+                self.kw("UPDATE"),
+                self.ident(table_name),
+                self.kw("SET"),
+                self.list(assignments, |a| self.assignment(a)),
+                self.where_clause(r#where),
+                self.returning(returning)
+            ),
+            Statement::Insert { table_name, columns, source, on_conflict, returning } => {
+                let columns = if columns.is_empty() {
+                    String::new()
+                } else {
+                    format!(" ({})", self.list(columns, |c| self.ident(c)))
+                };
+                let on_conflict = match on_conflict {
+                    Some(on_conflict) => format!(" {}", self.on_conflict(on_conflict)),
+                    None => String::new(),
+                };
+                format!(
+                    "{} {}{} {}{}{}",
+                    self.kw("INSERT INTO"),
+                    self.ident(table_name),
+                    columns,
+                    self.insert_source(source),
+                    on_conflict,
+                    self.returning(returning)
+                )
+            }
+            Statement::Delete { table_name, r#where, returning } => format!(
+                "{} {}{}{}",
+                self.kw("DELETE FROM"),
+                self.ident(table_name),
+                self.where_clause(r#where),
+                self.returning(returning)
+            ),
+            Statement::Values(rows) => self.values(rows),
+            Statement::Union { left, right, all } => format!(
+                "{} {} {}",
+                self.statement(left),
+                self.kw(if *all { "UNION ALL" } else { "UNION" }),
+                self.statement(right)
+            ),
+            Statement::WithQuery { ctes, body } => {
+                format!("{} {} {}", self.kw("WITH"), self.list(ctes, |c| self.cte(c)), self.statement(body))
+            }
+            Statement::Explain { analyze, inner } => {
+                format!("{} {}", self.kw(if *analyze { "EXPLAIN ANALYZE" } else { "EXPLAIN" }), self.statement(inner))
+            }
+            Statement::Describe { table } => format!("{} {}", self.kw("DESCRIBE"), self.ident(table)),
+        }
+    }
+
+    // The optional clauses shared by SELECT and SELECT *, each with a leading space.
+    fn select_clauses(
+        &self,
+        r#where: &Option<Expression>,
+        group_by: &[GroupByKind],
+        having: &Option<Expression>,
+        orderby: &[OrderByItem],
+        limit: &Option<Limit>,
+        offset: &Option<u64>,
+    ) -> String {
+        let mut sql = self.where_clause(r#where);
+        if !group_by.is_empty() {
+            sql += &format!(" {} {}", self.kw("GROUP BY"), self.list(group_by, |item| self.group_by_item(item)));
+        }
+        if let Some(having) = having {
+            sql += &format!(" {} {}", self.kw("HAVING"), self.expr(having));
+        }
+        if !orderby.is_empty() {
+            sql += &format!(" {} {}", self.kw("ORDER BY"), self.list(orderby, |item| self.order_by_item(item)));
+        }
+        if let Some(limit) = limit {
+            sql += &format!(" {} {}", self.kw("LIMIT"), self.kw(&limit.to_string()));
+        }
+        if let Some(offset) = offset {
+            sql += &format!(" {} {}", self.kw("OFFSET"), offset);
+        }
+        sql
+    }
+
+    // One GROUP BY item: an expression or a ROLLUP/CUBE/GROUPING SETS list.
+    pub(crate) fn group_by_item(&self, item: &GroupByKind) -> String {
+        match item {
+            GroupByKind::Expression(expr) => self.expr(expr),
+            GroupByKind::Rollup(exprs) => format!("{} ({})", self.kw("ROLLUP"), self.list(exprs, |e| self.expr(e))),
+            GroupByKind::Cube(exprs) => format!("{} ({})", self.kw("CUBE"), self.list(exprs, |e| self.expr(e))),
+            GroupByKind::GroupingSets(sets) => {
+                let sets = self.list(sets, |set| format!("({})", self.list(set, |e| self.expr(e))));
+                format!("{} ({})", self.kw("GROUPING SETS"), sets)
+            }
+        }
+    }
+
+    // The optional WHERE clause, with a leading space.
+    fn where_clause(&self, condition: &Option<Expression>) -> String {
+        match condition {
+            Some(condition) => format!(" {} {}", self.kw("WHERE"), self.expr(condition)),
+            None => String::new(),
+        }
+    }
+
+    // The optional RETURNING list, with a leading space.
+    fn returning(&self, returning: &[Expression]) -> String {
+        if returning.is_empty() {
+            return String::new();
+        }
+        format!(" {} {}", self.kw("RETURNING"), self.list(returning, |e| self.expr(e)))
+    }
+
+    // VALUES followed by the row tuples.
+    fn values(&self, rows: &[Vec<Expression>]) -> String {
+        let rows = self.list(rows, |row| format!("({})", self.list(row, |e| self.expr(e))));
+        format!("{} {}", self.kw("VALUES"), rows)
+    }
+
+    // One ORDER BY item with its optional direction.
+    pub(crate) fn order_by_item(&self, item: &OrderByItem) -> String {
+        match item.order {
+            Some(SortOrder::Asc) => format!("{} {}", self.expr(&item.expr), self.kw("ASC")),
+            Some(SortOrder::Desc) => format!("{} {}", self.expr(&item.expr), self.kw("DESC")),
+            None => self.expr(&item.expr),
+        }
+    }
+
+    // One SET item (column = value).
+    pub(crate) fn assignment(&self, assignment: &Assignment) -> String {
+        format!("{} = {}", self.ident(&assignment.column), self.expr(&assignment.value))
+    }
+
+    // The rows an INSERT adds: VALUES, DEFAULT VALUES, or a query.
+    pub(crate) fn insert_source(&self, source: &InsertSource) -> String {
+        match source {
+            InsertSource::Values(rows) => self.values(rows),
+            InsertSource::DefaultValues => self.kw("DEFAULT VALUES"),
+            InsertSource::Query(query) => self.statement(query),
+        }
+    }
+
+    // The upsert clause of an INSERT (ON CONFLICT ... or ON DUPLICATE KEY UPDATE ...).
+    pub(crate) fn on_conflict(&self, on_conflict: &OnConflict) -> String {
+        let target = if on_conflict.target.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", self.list(&on_conflict.target, |c| self.ident(c)))
+        };
+        match &on_conflict.action {
+            ConflictAction::DoNothing => format!("{}{} {}", self.kw("ON CONFLICT"), target, self.kw("DO NOTHING")),
+            ConflictAction::DoUpdate(assignments) => format!(
+                "{}{} {} {}",
+                self.kw("ON CONFLICT"),
+                target,
+                self.kw("DO UPDATE SET"),
+                self.list(assignments, |a| self.assignment(a))
+            ),
+            ConflictAction::DuplicateKeyUpdate(assignments) => format!(
+                "{} {}",
+                self.kw("ON DUPLICATE KEY UPDATE"),
+                self.list(assignments, |a| self.assignment(a))
+            ),
+        }
+    }
+
+    // The optional INTO target of a SELECT, with a leading space.
+    fn into(&self, into: &Option<String>) -> String {
+        match into {
+            Some(table) => format!(" {} {}", self.kw("INTO"), self.ident(table)),
+            None => String::new(),
+        }
+    }
+
+    // The FROM target: a table name or a table-valued function call.
+    pub(crate) fn table_source(&self, source: &TableSource) -> String {
+        match source {
+            TableSource::Table(name) => self.ident(name),
+            TableSource::Function { name, args, alias } => {
+                let mut sql = format!("{}({})", self.function_name(name), self.list(args, |arg| self.expr(arg)));
+                if let Some(alias) = alias {
+                    sql += &format!(" {} {}", self.kw("AS"), self.ident(alias));
+                }
+                sql
+            }
+        }
+    }
+
+    // One WITH definition (name AS (query)).
+    pub(crate) fn cte(&self, cte: &Cte) -> String {
+        format!("{} {} ({})", self.ident(&cte.name), self.kw("AS"), self.statement(&cte.query))
+    }
+
+    // One CREATE TABLE column definition with its collation, constraints, and comment.
+    pub(crate) fn column(&self, column: &TableColumn) -> String {
+        let mut sql = format!("{} {}", self.ident(&column.column_name), self.kw(&column.column_type.to_string()));
+        if let Some(collation) = &column.collation {
+            sql += &format!(" {} {}", self.kw("COLLATE"), quote_collation(collation));
+        }
+        for constraint in &column.constraints {
+            sql += &format!(" {}", self.constraint(constraint));
+        }
+        if let Some(comment) = &column.comment {
+            sql += &format!(" {} {}", self.kw("COMMENT"), quote_string(comment));
+        }
+        sql
+    }
+
+    // One column constraint (e.g., NOT NULL, CHECK (...), REFERENCES t(id)).
+    pub(crate) fn constraint(&self, constraint: &Constraint) -> String {
+        match constraint {
+            Constraint::PrimaryKey => self.kw("PRIMARY KEY"),
+            Constraint::NotNull => self.kw("NOT NULL"),
+            Constraint::AutoIncrement => self.kw("AUTO_INCREMENT"),
+            Constraint::Check(expr) => format!("{} ({})", self.kw("CHECK"), self.expr(expr)),
+            Constraint::Default(expr) => format!("{} {}", self.kw("DEFAULT"), self.expr(expr)),
+            Constraint::Generated { expr, stored } => format!(
+                "{} ({}){}",
+                self.kw("GENERATED ALWAYS AS"),
+                self.expr(expr),
+                if *stored { format!(" {}", self.kw("STORED")) } else { String::new() }
+            ),
+            Constraint::References { table, column, referential_actions } => {
+                let mut sql = format!("{} {}", self.kw("REFERENCES"), self.ident(table));
+                if let Some(column) = column {
+                    sql += &format!("({})", self.ident(column));
+                }
+                if let Some(action) = &referential_actions.on_delete {
+                    sql += &format!(" {} {}", self.kw("ON DELETE"), self.kw(&action.to_string()));
+                }
+                if let Some(action) = &referential_actions.on_update {
+                    sql += &format!(" {} {}", self.kw("ON UPDATE"), self.kw(&action.to_string()));
+                }
+                sql
+            }
+        }
+    }
+
+    // Format an expression. Operator chains are walked with an explicit work stack rather than
+    // recursion, so even a very deep tree (e.g., thousands of a + a + ...) formats without overflowing
+    // the call stack; other nodes are formatted by node().
+    pub(crate) fn expr(&self, expr: &Expression) -> String {
+        let mut sql = String::new();
+        let mut stack = vec![SqlPart::Expr(expr, false)];
+        while let Some(part) = stack.pop() {
+            let (expr, parenthesized) = match part {
+                SqlPart::Text(text) => {
+                    push_sql(&mut sql, &text);
+                    continue;
+                }
+                SqlPart::Expr(expr, parenthesized) => (expr, parenthesized),
+            };
+            // Parts are pushed in reverse so they pop off in output order. Display style wraps every
+            // binary operation, so its operands never need extra parentheses.
+            if parenthesized || (self.display && matches!(expr, Expression::BinaryOperation { .. })) {
+                sql.push('(');
+                stack.push(SqlPart::Text(")".to_string()));
+            }
+            match expr {
+                // An operand of equal precedence keeps its parentheses unless the operator groups
+                // toward that side (e.g., a - (b - c), or (a = b) = c for a non-associative one).
+                Expression::BinaryOperation { left_operand, operator, right_operand } => {
+                    let precedence = operator.precedence();
+                    let associativity = operator.associativity();
+                    let display = self.display;
+                    let right_precedence = right_operand.precedence();
+                    let left_precedence = left_operand.precedence();
+                    let right_parenthesized = right_precedence < precedence
+                        || (right_precedence == precedence && associativity != Associativity::Right);
+                    let left_parenthesized = left_precedence < precedence
+                        || (left_precedence == precedence && associativity != Associativity::Left);
+                    stack.push(SqlPart::Expr(right_operand, !display && right_parenthesized));
+                    stack.push(SqlPart::Text(format!(" {} ", self.kw(&operator.to_string()))));
+                    stack.push(SqlPart::Expr(left_operand, !display && left_parenthesized));
+                }
+                Expression::UnaryOperation { operator, operand } => {
+                    stack.push(SqlPart::Expr(operand, !self.display && operand.precedence() < expr.precedence()));
+                    match operator {
+                        UnaryOperator::Not => push_sql(&mut sql, &format!("{} ", self.kw("NOT"))),
+                        // Keep a space between stacked signs, e.g. "- -5"; Display style always spaces them.
+                        // push_sql also spaces a sign before other operands that start with -, e.g. "- -1".
+                        _ if self.display || matches!(**operand, Expression::UnaryOperation { .. }) => {
+                            push_sql(&mut sql, &format!("{} ", operator))
+                        }
+                        _ => push_sql(&mut sql, &operator.to_string()),
+                    }
+                }
+                _ => push_sql(&mut sql, &self.node(expr)),
+            }
+        }
+        sql
+    }
+
+    // Format an expression node other than a binary or unary operation (those are handled by expr()).
+    fn node(&self, expr: &Expression) -> String {
+        let sql = match expr {
+            Expression::Number(n) => n.to_string(),
+            Expression::SignedNumber(n) => n.to_string(),
+            Expression::String(s) => self.string(s),
+            Expression::Bool(b) if self.display => b.to_string(),
+            Expression::Bool(b) => self.kw(if *b { "TRUE" } else { "FALSE" }),
+            Expression::Identifier(id) => self.ident(id),
+            Expression::CompoundIdentifier(parts) => {
+                parts.iter().map(|part| self.ident(part)).collect::<Vec<_>>().join(".")
+            }
+            Expression::Parameter(kind) => kind.to_string(),
+            Expression::Wildcard => "*".to_string(),
+            Expression::CurrentDate => self.kw("CURRENT_DATE"),
+            Expression::CurrentTimestamp => self.kw("CURRENT_TIMESTAMP"),
+            Expression::Default => self.kw("DEFAULT"),
+            Expression::TypedLiteral { ty, value } => format!("{} {}", self.kw(&ty.to_string()), self.string(value)),
+            Expression::BinaryOperation { .. } | Expression::UnaryOperation { .. } => self.expr(expr),
+            Expression::FunctionCall { name, distinct, args, filter } => format!(
+                "{}({}{}){}",
+                self.function_name(name),
+                if *distinct { self.kw("DISTINCT ") } else { String::new() },
+                self.list(args, |arg| self.expr(arg)),
+                match filter {
+                    Some(condition) => format!(" {} ({} {})", self.kw("FILTER"), self.kw("WHERE"), self.expr(condition)),
+                    None => String::new(),
+                }
+            ),
+            Expression::WindowFunction { func, partition_by, order_by } => {
+                let mut spec = Vec::new();
+                if !partition_by.is_empty() {
+                    spec.push(format!("{} {}", self.kw("PARTITION BY"), self.list(partition_by, |e| self.expr(e))));
+                }
+                if !order_by.is_empty() {
+                    spec.push(format!("{} {}", self.kw("ORDER BY"), self.list(order_by, |item| self.order_by_item(item))));
+                }
+                format!("{} {} ({})", self.expr(func), self.kw("OVER"), spec.join(" "))
+            }
+            Expression::Alias { expr, alias } => format!("{} {} {}", self.expr(expr), self.kw("AS"), self.ident(alias)),
+            Expression::List(items) => format!("({})", self.list(items, |item| self.expr(item))),
+            Expression::Collate { expr: inner, collation } => {
+                format!(
+                    "{} {} {}",
+                    self.operand(expr, inner, false),
+                    self.kw("COLLATE"),
+                    quote_collation(collation)
+                )
+            }
+            Expression::Cast { expr: inner, data_type } => {
+                format!("{}::{}", self.operand(expr, inner, false), self.kw(&data_type.to_string()))
+            }
+            Expression::Quantified { expr: inner, op, quantifier, subquery } => format!(
+                "{} {} {} ({})",
+                self.operand(expr, inner, false),
+                self.kw(&op.to_string()),
+                self.kw(&quantifier.to_string()),
+                self.statement(subquery)
+            ),
+            Expression::InList { expr: inner, list, negated } => format!(
+                "{} {} ({})",
+                self.operand(expr, inner, false),
+                self.kw(if *negated { "NOT IN" } else { "IN" }),
+                self.list(list, |item| self.expr(item))
+            ),
+            Expression::InSubquery { expr: inner, subquery, negated } => format!(
+                "{} {} ({})",
+                self.operand(expr, inner, false),
+                self.kw(if *negated { "NOT IN" } else { "IN" }),
+                self.statement(subquery)
+            ),
+            Expression::Like { expr: inner, pattern, negated, escape } => {
+                let mut sql = format!(
+                    "{} {} {}",
+                    self.operand(expr, inner, false),
+                    self.kw(if *negated { "NOT LIKE" } else { "LIKE" }),
+                    self.operand(expr, pattern, true)
+                );
+                if let Some(c) = escape {
+                    sql += &format!(" {} {}", self.kw("ESCAPE"), self.string(&c.to_string()));
+                }
+                sql
+            }
+            Expression::Between { expr: inner, low, high, negated } => format!(
+                "{} {} {} {} {}",
+                self.operand(expr, inner, false),
+                self.kw(if *negated { "NOT BETWEEN" } else { "BETWEEN" }),
+                self.operand(expr, low, true),
+                self.kw("AND"),
+                self.operand(expr, high, true)
+            ),
+            Expression::IsNull { expr: inner, negated } => format!(
+                "{} {}",
+                self.operand(expr, inner, false),
+                self.kw(if *negated { "IS NOT NULL" } else { "IS NULL" })
+            ),
+            Expression::IsBool { expr: inner, value, negated } => format!(
+                "{} {} {}",
+                self.operand(expr, inner, false),
+                self.kw(if *negated { "IS NOT" } else { "IS" }),
+                self.kw(truth_value(*value))
+            ),
+        };
+        // Display style wraps predicates the same way it wraps binary operations.
+        let predicate = matches!(
+            expr,
+            Expression::Quantified { .. }
+                | Expression::InList { .. }
+                | Expression::InSubquery { .. }
+                | Expression::Like { .. }
+                | Expression::Between { .. }
+                | Expression::IsNull { .. }
+                | Expression::IsBool { .. }
+        );
+        if self.display && predicate {
+            format!("({})", sql)
+        } else {
+            sql
+        }
+    }
+
+    // A string literal: SQL single quotes, or unescaped double quotes in Display style.
+    fn string(&self, s: &str) -> String {
+        if self.display {
+            format!("\"{}\"", s)
+        } else {
+            quote_string(s)
+        }
+    }
+
+    // Format an operand of parent, parenthesizing it if it binds more loosely than parent.
+    // Right-hand operands are also parenthesized at equal precedence to keep left associativity.
+    fn operand(&self, parent: &Expression, operand: &Expression, right_side: bool) -> String {
+        let needs_parentheses = if self.display {
+            false
+        } else if right_side {
+            operand.precedence() <= parent.precedence()
+        } else {
+            operand.precedence() < parent.precedence()
+        };
+        if needs_parentheses {
+            format!("({})", self.expr(operand))
+        } else {
+            self.expr(operand)
+        }
+    }
+}
+
+// Append text to SQL being built, keeping a space between two minus signs: "--" starts a line comment,
+// so -(-1) must be written "- -1" rather than "--1".
+fn push_sql(sql: &mut String, text: &str) {
+    if sql.ends_with('-') && text.starts_with('-') {
+        sql.push(' ');
+    }
+    sql.push_str(text);
+}
@@ -0,0 +1,15 @@
+// lib.rs - Library entry point for the SQL Parser.
+// Exposes the tokenizer, parser, and AST modules so they can be used outside the CLI binary
+// (e.g., by other programs or by the unit tests in tests.rs).
+// The CLI in main.rs is a thin wrapper around this library.
+
+pub mod statement; // Defines the AST structures (e.g., Statement, Expression).
+pub mod token; // Defines the Token enum for lexical analysis.
+pub mod tokenizer; // Converts input strings into tokens.
+pub mod parser; // Parses tokens into an AST.
+pub mod format; // Formats ASTs as SQL with configurable identifier quoting and keyword case.
+
+// Unit tests for the tokenizer, Pratt parser, and SQL parser.
+// tests.rs wraps its tests in its own #[cfg(test)] module, hence the allow.
+#[allow(clippy::module_inception)]
+mod tests;
@@ -4,18 +4,12 @@
 // Implements Functionality #20 (1 point): CLI for user interaction.
 // Author: Fuad Mahmud Shad (fuad.mahmud.shad@academic.email)
 
-// Import required modules for the parser, tokenizer, token, and statement definitions.
-// These modules contain the core logic for tokenizing and parsing SQL queries.
-mod statement; // Defines the AST structures (e.g., Statement, Expression).
-mod token; // Defines the Token enum for lexical analysis.
-mod tokenizer; // Converts input strings into tokens.
-mod parser; // Parses tokens into an AST.
-
 // Import standard library modules for I/O operations.
 // io is used for reading user input and writing output to the console.
 use std::io::{self, Write};
-// Import the Parser struct from the parser module to parse SQL queries.
-use parser::Parser;
+// Import the Parser struct from the sql_parser library (src/lib.rs) to parse SQL queries.
+// The library contains the core logic for tokenizing and parsing SQL queries.
+use sql_parser::parser::Parser;
 
 // Main function: Entry point of the CLI application.
 // Sets up an interactive loop to read user input, parse SQL queries, and display results.
@@ -65,8 +59,9 @@ fn main() {
         // The Parser will tokenize and parse the input into an AST.
         let mut parser = Parser::new(input);
         // Parse the input and handle the result (Ok or Err).
+        // parse_lenient lets interactive users omit the final semicolon.
         // match is used to handle both successful parsing and errors gracefully.
-        match parser.parse() {
+        match parser.parse_lenient() {
             // If parsing succeeds, print the parsed Statement (AST) in debug format.
             // {:#?} provides a pretty-printed, detailed view of the AST for clarity.
             Ok(statement) => println!("Parsed Statement: {:#?}", statement),
@@ -13,11 +13,11 @@
 // Supports SELECT * syntax for bonus points (2 points).
 
 // Import necessary types from other modules for parsing and AST construction.
-use crate::statement::{Statement, Expression, BinaryOperator, UnaryOperator, TableColumn, DBType, Constraint};
-use crate::token::{Token, Keyword};
-use crate::tokenizer::{Tokenizer};
-// Use Peekable to inspect tokens without consuming them.
-use std::iter::Peekable;
+use crate::statement::{Statement, Expression, BinaryOperator, UnaryOperator, TableColumn, DBType, Constraint, ReferentialAction, ReferentialActions, ParamKind, OrderByItem, GroupByKind, SortOrder, Cte, Assignment, Quantifier, Limit, InsertSource, TableSource, OnConflict, ConflictAction};
+use crate::token::{Token, Keyword, Span, Spanned};
+use crate::tokenizer::{Tokenizer, TokenizerError, TokenizerOptions};
+// Use VecDeque to buffer upcoming tokens for multi-token lookahead.
+use std::collections::VecDeque;
 
 // Define possible parsing errors for invalid tokens or syntax.
 #[derive(Debug)]
@@ -25,43 +25,342 @@ pub enum ParseError {
     UnexpectedToken(Token),
     InvalidVarcharLength,
     InvalidColumnType,
+    Tokenizer(TokenizerError), // The input could not be tokenized (only reported by try_parse).
+    DepthLimitExceeded, // Expression nesting exceeds ParserOptions::max_depth.
+    ChainedComparison(Token), // A comparison applied to an unparenthesized comparison (e.g., `a < b < c`).
+    InvalidTypedLiteral(DBType), // Typed literal with an empty value (e.g., DATE '').
+    InvalidEscape(String), // LIKE ESCAPE string that is not exactly one character.
+    IdentifierTooLong(String), // Name longer than ParserOptions::max_identifier_length.
+    WrongArgumentCount { function: String, expected: String, found: usize }, // e.g., NULLIF(a); expected is "2" or "at least 1".
+    EmptyParentheses, // () where an expression is expected (e.g., SELECT ();).
+    FilterOnNonAggregate(String), // FILTER (WHERE ...) after a call that is not an aggregate (e.g., UPPER(x)).
+    DuplicateCollation(String), // A column with a second COLLATE clause; holds the column name.
+    InvalidDelimiter(Token), // ParserOptions::delimiter is an operator (e.g., /), which expressions need.
+    NotCreateTable, // parse_table_columns got a CREATE TABLE without a column list (CREATE TABLE ... AS SELECT).
 }
 
 // Implement Display for ParseError to show user-friendly error messages.
+// Tokens are shown as SQL text; the end-of-input and invalid-character markers get their own wording.
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            ParseError::UnexpectedToken(Token::Eof) => write!(f, "Unexpected end of input"),
+            ParseError::UnexpectedToken(Token::Invalid(c)) => write!(f, "Unexpected character: {}", c),
             ParseError::UnexpectedToken(t) => write!(f, "Unexpected token: {}", t),
             ParseError::InvalidVarcharLength => write!(f, "Invalid VARCHAR length"),
             ParseError::InvalidColumnType => write!(f, "Invalid column type"),
+            ParseError::Tokenizer(e) => write!(f, "Tokenizer error: {}", e),
+            ParseError::DepthLimitExceeded => write!(f, "Expression nested too deeply"),
+            ParseError::InvalidTypedLiteral(ty) => write!(f, "Invalid {} literal", ty),
+            ParseError::InvalidEscape(s) => write!(f, "ESCAPE must be a single character, got \"{}\"", s),
+            ParseError::IdentifierTooLong(name) => write!(f, "Identifier too long: {}", name),
+            ParseError::WrongArgumentCount { function, expected, found } => {
+                write!(f, "{} expects {} argument(s), got {}", function, expected, found)
+            }
+            ParseError::EmptyParentheses => write!(f, "Empty parentheses where an expression is expected"),
+            ParseError::FilterOnNonAggregate(function) => {
+                write!(f, "FILTER is only allowed on aggregate functions, not {}", function)
+            }
+            ParseError::DuplicateCollation(column) => write!(f, "Column {} has more than one COLLATE clause", column),
+            ParseError::NotCreateTable => write!(f, "Expected CREATE TABLE with a column list"),
+            ParseError::InvalidDelimiter(t) => write!(f, "An operator cannot be the statement delimiter: {}", t),
+            ParseError::ChainedComparison(t) => {
+                write!(f, "Chained comparison at {}; use parentheses to group comparisons", t)
+            }
+        }
+    }
+}
+
+// Default maximum nesting of expressions (parentheses, unary operators), so hostile input
+// like "((((...))))" produces an error instead of overflowing the stack.
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+// Check the argument count of the few built-in functions with a fixed arity; names are matched
+// case-insensitively, and any other function accepts any number of arguments.
+fn check_argument_count(function: &str, found: usize) -> Result<(), ParseError> {
+    let expected = match function.to_ascii_uppercase().as_str() {
+        "COALESCE" if found == 0 => "at least 1",
+        "NULLIF" if found != 2 => "2",
+        _ => return Ok(()),
+    };
+    Err(ParseError::WrongArgumentCount { function: function.to_string(), expected: expected.to_string(), found })
+}
+
+// Aggregate functions, the only calls that accept FILTER (WHERE ...); matched case-insensitively.
+const AGGREGATE_FUNCTIONS: &[&str] = &["COUNT", "SUM", "AVG", "MIN", "MAX", "ARRAY_AGG", "STRING_AGG", "BOOL_AND", "BOOL_OR", "EVERY"];
+
+// Define Associativity enum for how a chain of operators with equal precedence groups.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Associativity {
+    Left, // a - b - c is (a - b) - c.
+    Right, // 2 ^ 3 ^ 2 is 2 ^ (3 ^ 2).
+    NonAssociative, // a < b < c is rejected (ParseError::ChainedComparison).
+}
+
+// Precedence shared by the comparison operators (=, !=, <, <=, >, >=) and by ALL/ANY/SOME comparisons.
+pub const COMPARISON_PRECEDENCE: u8 = 30;
+
+// Binary operators as data: the token, the AST operator it builds, its precedence, and its associativity.
+// get_precedence, parse_infix, BinaryOperator::precedence (used when formatting), and the chained
+// comparison check all read this table, so a new binary operator is one entry here.
+// IN, LIKE, BETWEEN, and COLLATE have their own syntax and are handled separately.
+const OPERATORS: &[(Token, BinaryOperator, u8, Associativity)] = &[
+    (Token::Keyword(Keyword::Or), BinaryOperator::Or, 10, Associativity::Left),
+    (Token::Keyword(Keyword::And), BinaryOperator::And, 20, Associativity::Left),
+    (Token::Equal, BinaryOperator::Equal, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+    (Token::NotEqual, BinaryOperator::NotEqual, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+    (Token::GreaterThan, BinaryOperator::GreaterThan, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+    (Token::GreaterThanOrEqual, BinaryOperator::GreaterThanOrEqual, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+    (Token::LessThan, BinaryOperator::LessThan, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+    (Token::LessThanOrEqual, BinaryOperator::LessThanOrEqual, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+    (Token::Plus, BinaryOperator::Plus, 50, Associativity::Left),
+    (Token::Minus, BinaryOperator::Minus, 50, Associativity::Left),
+    (Token::Star, BinaryOperator::Multiply, 60, Associativity::Left),
+    (Token::Divide, BinaryOperator::Divide, 60, Associativity::Left),
+    // Above unary signs (see UnaryOperator::precedence); right-associative as in math, so 2 ^ 3 ^ 2 is 2 ^ (3 ^ 2).
+    (Token::Caret, BinaryOperator::Power, 70, Associativity::Right),
+];
+
+// Look up the OPERATORS entry for a token, if it is a binary operator.
+fn operator_entry(token: &Token) -> Option<&'static (Token, BinaryOperator, u8, Associativity)> {
+    OPERATORS.iter().find(|(op_token, ..)| op_token == token)
+}
+
+impl BinaryOperator {
+    // This operator's OPERATORS entry; every BinaryOperator has one.
+    fn entry(&self) -> &'static (Token, BinaryOperator, u8, Associativity) {
+        OPERATORS.iter().find(|(_, operator, ..)| operator == self).expect("every BinaryOperator is in OPERATORS")
+    }
+
+    // Binding power of the operator, as the parser uses it.
+    pub fn precedence(&self) -> u8 {
+        self.entry().2
+    }
+
+    // How a chain of operators with this precedence groups.
+    pub fn associativity(&self) -> Associativity {
+        self.entry().3
+    }
+}
+
+// Number of tokens Parser::debug_dump shows on each side of the current position.
+const DUMP_CONTEXT: usize = 5;
+
+// Parse a single statement without ever panicking, whatever the input.
+// Unlike Parser::new, tokenizer failures are reported as ParseError::Tokenizer
+// rather than being turned into an empty token stream.
+pub fn try_parse(input: &str) -> Result<Statement, ParseError> {
+    let tokens = Tokenizer::new(input).tokenize_with_spans().map_err(ParseError::Tokenizer)?;
+    Parser::from_spanned_tokens(tokens, ParserOptions::default()).parse()
+}
+
+// Parse the first statement of input and return it with the byte offset just past its semicolon,
+// so the caller can continue with &input[offset..] (e.g., SQL embedded in a larger document).
+// Tokenizer failures anywhere in input are reported as ParseError::Tokenizer, as in try_parse.
+pub fn parse_one(input: &str) -> Result<(Statement, usize), ParseError> {
+    parse_one_with_options(input, ParserOptions::default())
+}
+
+// Like parse_one, but with ParserOptions (e.g., a different delimiter or tokenizer options).
+pub fn parse_one_with_options(input: &str, options: ParserOptions) -> Result<(Statement, usize), ParseError> {
+    let tokens = Tokenizer::with_options(input, options.tokenizer.clone()).tokenize_with_spans().map_err(ParseError::Tokenizer)?;
+    let Spanned { node, span } = Parser::from_spanned_tokens(tokens, options).parse_spanned()?;
+    Ok((node, span.end))
+}
+
+// Parse a CREATE TABLE statement and return just its table name and columns, e.g. for schema tooling.
+// Any other statement is an UnexpectedToken error at its first token; CREATE TABLE ... AS SELECT is NotCreateTable.
+pub fn parse_table_columns(input: &str) -> Result<(String, Vec<TableColumn>), ParseError> {
+    parse_table_columns_with_options(input, ParserOptions::default())
+}
+
+// Like parse_table_columns, but with ParserOptions.
+pub fn parse_table_columns_with_options(input: &str, options: ParserOptions) -> Result<(String, Vec<TableColumn>), ParseError> {
+    let tokens = Tokenizer::with_options(input, options.tokenizer.clone()).tokenize_with_spans().map_err(ParseError::Tokenizer)?;
+    let mut parser = Parser::from_spanned_tokens(tokens, options);
+    if parser.current_token() != Some(&Token::Keyword(Keyword::Create)) {
+        return Err(ParseError::UnexpectedToken(parser.current_token().cloned().unwrap_or(Token::Eof)));
+    }
+    match parser.parse()? {
+        Statement::CreateTable { table_name, column_list, .. } => Ok((table_name, column_list)),
+        _ => Err(ParseError::NotCreateTable),
+    }
+}
+
+// Parse every statement of a script, each with the span from its first token to its semicolon,
+// e.g. for an editor highlighting individual statements. The last statement may omit its semicolon.
+pub fn parse_script(input: &str) -> Result<Vec<Spanned<Statement>>, ParseError> {
+    parse_script_with_options(input, ParserOptions::default())
+}
+
+// Like parse_script, but with ParserOptions; statements then end at the configured delimiter.
+pub fn parse_script_with_options(input: &str, options: ParserOptions) -> Result<Vec<Spanned<Statement>>, ParseError> {
+    let tokens = Tokenizer::with_options(input, options.tokenizer.clone()).tokenize_with_spans().map_err(ParseError::Tokenizer)?;
+    let mut parser = Parser::from_spanned_tokens(tokens, options);
+    parser.eof_terminates = true;
+    let mut statements = Vec::new();
+    while !matches!(parser.peek_nth(0), Some(Token::Eof) | None) {
+        statements.push(parser.parse_spanned()?);
+    }
+    Ok(statements)
+}
+
+// Options that relax parsing rules; the defaults give the strict behavior.
+#[derive(Debug, Clone)]
+pub struct ParserOptions {
+    pub allow_trailing_comma: bool, // Accept a trailing comma before a list terminator (e.g., `a, b, FROM`).
+    pub allow_keywords_as_identifiers: bool, // Accept non-reserved keywords as names (e.g., `SELECT key FROM t`).
+    pub allow_implicit_alias: bool, // Accept a select-list alias without AS (e.g., `SELECT age years FROM t`).
+    pub tokenizer: TokenizerOptions, // Options for the tokenizer that Parser::with_options runs.
+    pub max_depth: usize, // Deepest expression (or EXPLAIN) nesting accepted before DepthLimitExceeded.
+    pub delimiter: Token, // Statement terminator; an operator token (e.g., `/`) is rejected with ParseError::InvalidDelimiter.
+    pub max_identifier_length: Option<usize>, // Longest accepted table/column/alias name in characters; None is unlimited.
+    pub fold_unary_minus: bool, // Parse a minus applied to an integer literal (e.g., DEFAULT -1) as Expression::SignedNumber.
+}
+
+impl Default for ParserOptions {
+    fn default() -> Self {
+        ParserOptions {
+            allow_trailing_comma: false,
+            allow_keywords_as_identifiers: false,
+            allow_implicit_alias: false,
+            tokenizer: TokenizerOptions::default(),
+            max_depth: DEFAULT_MAX_DEPTH,
+            delimiter: Token::Semicolon,
+            max_identifier_length: None,
+            fold_unary_minus: false,
         }
     }
 }
 
-// Parser struct holds a Peekable iterator of tokens for parsing.
+// Optional clauses shared by SELECT and SELECT *, collected while parsing.
+#[derive(Default)]
+struct SelectClauses {
+    r#where: Option<Expression>,
+    group_by: Vec<GroupByKind>,
+    having: Option<Expression>,
+    orderby: Vec<OrderByItem>,
+    limit: Option<Limit>,
+    offset: Option<u64>,
+}
+
+// Parser struct holds an iterator of tokens plus a small lookahead buffer for parsing.
 pub struct Parser {
-    tokens: Peekable<std::vec::IntoIter<Token>>,
+    tokens: std::vec::IntoIter<(Token, Span)>,
+    lookahead: VecDeque<(Token, Span)>, // Tokens peeked but not yet consumed.
+    last_span: Span, // Span of the most recently consumed token.
+    options: ParserOptions, // Parsing options (strict by default).
+    eof_terminates: bool, // Set by parse_lenient: end of input may stand in for the semicolon.
+    depth: usize, // Current expression and EXPLAIN nesting depth, bounded by options.max_depth.
+    recent: VecDeque<Token>, // The last few consumed tokens, oldest first, for debug_dump.
 }
 
 impl Parser {
     // Create a new Parser from an input string, tokenizing it first.
     pub fn new(input: &str) -> Self {
-        let mut tokenizer = Tokenizer::new(input);
+        Parser::with_options(input, ParserOptions::default())
+    }
+
+    // Create a new Parser with custom parsing options.
+    pub fn with_options(input: &str, options: ParserOptions) -> Self {
+        let mut tokenizer = Tokenizer::with_options(input, options.tokenizer.clone());
         // Tokenize input; return [Eof] on error to avoid panics.
-        let tokens = tokenizer.tokenize().unwrap_or_else(|_| vec![Token::Eof]);
+        let tokens = tokenizer.tokenize_with_spans().unwrap_or_else(|_| vec![(Token::Eof, Span::default())]);
+        Parser::from_spanned_tokens(tokens, options)
+    }
+
+    // Create a Parser from a pre-tokenized stream (e.g., after preprocessing the tokens).
+    // The tokens carry no source positions, so all spans are Span::default().
+    pub fn from_tokens(tokens: Vec<Token>) -> Self {
+        let tokens = tokens.into_iter().map(|token| (token, Span::default())).collect();
+        Parser::from_spanned_tokens(tokens, ParserOptions::default())
+    }
+
+    // Create a Parser over an already tokenized input; new, from_tokens, and try_parse share this.
+    fn from_spanned_tokens(mut tokens: Vec<(Token, Span)>, options: ParserOptions) -> Self {
+        // Hints are for tooling that reads the token stream; the grammar ignores them.
+        tokens.retain(|(token, _)| !matches!(token, Token::Hint(_)));
+        // Custom keywords are also for tooling; the grammar has no rules for them, so they are names
+        // here (e.g., a table called upsert), spelled as configured.
+        for (token, _) in tokens.iter_mut() {
+            if let Token::CustomKeyword(word) = token {
+                *token = Token::Identifier(std::mem::take(word));
+            }
+        }
         Parser {
-            tokens: tokens.into_iter().peekable(),
+            tokens: tokens.into_iter(),
+            lookahead: VecDeque::new(),
+            last_span: Span::default(),
+            options,
+            eof_terminates: false,
+            depth: 0,
+            recent: VecDeque::with_capacity(DUMP_CONTEXT),
+        }
+    }
+
+    // Peek at the token n positions ahead (0 is the current token) without consuming anything.
+    // Buffers tokens from the iterator as needed, so any lookahead distance is supported.
+    pub fn peek_nth(&mut self, n: usize) -> Option<&Token> {
+        self.peek_nth_spanned(n).map(|(token, _)| token)
+    }
+
+    // Peek at the token n positions ahead together with its span.
+    fn peek_nth_spanned(&mut self, n: usize) -> Option<&(Token, Span)> {
+        while self.lookahead.len() <= n {
+            match self.tokens.next() {
+                Some(token) => self.lookahead.push_back(token),
+                None => break,
+            }
         }
+        self.lookahead.get(n)
+    }
+
+    // Span of the current (not yet consumed) token.
+    fn current_span(&mut self) -> Span {
+        let last_end = self.last_span.end;
+        self.peek_nth_spanned(0).map(|(_, span)| *span).unwrap_or(Span { start: last_end, end: last_end })
+    }
+
+    // Drain and return every token not consumed yet (including Eof), leaving the parser empty.
+    // Handy for debugging what was left over after a parse error.
+    pub fn remaining_tokens(&mut self) -> Vec<Token> {
+        let mut remaining: Vec<Token> = self.lookahead.drain(..).map(|(token, _)| token).collect();
+        remaining.extend(self.tokens.by_ref().map(|(token, _)| token));
+        remaining
+    }
+
+    // Show the tokens around the current position, e.g. after a parse error:
+    // "... FROM t WHERE >>> ) ;" (the last few consumed tokens, then the next few upcoming ones).
+    pub fn debug_dump(&self) -> String {
+        let consumed: Vec<String> = self.recent.iter().map(|token| token.to_string()).collect();
+        let upcoming: Vec<String> = self
+            .lookahead
+            .iter()
+            .chain(self.tokens.as_slice())
+            .take(DUMP_CONTEXT)
+            .map(|(token, _)| token.to_string())
+            .collect();
+        format!("... {} >>> {}", consumed.join(" "), upcoming.join(" "))
     }
 
     // Get the current token without consuming it.
     fn current_token(&mut self) -> Option<&Token> {
-        self.tokens.peek()
+        self.peek_nth(0)
     }
 
-    // Move to the next token.
+    // Move to the next token, taking it from the lookahead buffer first.
     fn advance(&mut self) {
-        self.tokens.next();
+        let consumed = match self.lookahead.pop_front() {
+            Some(token) => Some(token),
+            None => self.tokens.next(),
+        };
+        if let Some((token, span)) = consumed {
+            self.last_span = span;
+            if self.recent.len() == DUMP_CONTEXT {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(token);
+        }
     }
 
     // Check if the current token matches the expected one; error if not.
@@ -74,10 +373,59 @@ impl Parser {
         }
     }
 
+    // Expect a specific keyword; error if it is not the current token.
+    fn expect_keyword(&mut self, keyword: Keyword) -> Result<(), ParseError> {
+        self.expect_token(Token::Keyword(keyword))
+    }
+
+    // Consume the keyword if it is the current token; returns whether it did (for optional clauses).
+    fn consume_keyword(&mut self, keyword: Keyword) -> bool {
+        if self.current_token() == Some(&Token::Keyword(keyword)) {
+            self.advance();
+            true
+        } else {
+            false
+        }
+    }
+
+    // Reject a configured delimiter that is also an operator: `a / b` could not be told apart from
+    // `a` followed by a `/` terminator.
+    fn check_delimiter(&self) -> Result<(), ParseError> {
+        if self.get_precedence(&self.options.delimiter) > 0 {
+            return Err(ParseError::InvalidDelimiter(self.options.delimiter.clone()));
+        }
+        Ok(())
+    }
+
+    // Consume the statement terminator: the configured delimiter (a semicolon by default),
+    // or end of input when parsing leniently.
+    fn expect_statement_end(&mut self) -> Result<(), ParseError> {
+        let eof_terminates = self.eof_terminates;
+        let delimiter = self.options.delimiter.clone();
+        match self.current_token() {
+            Some(token) if *token == delimiter => {
+                self.advance();
+                Ok(())
+            }
+            Some(Token::Eof) if eof_terminates => Ok(()),
+            _ => Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof))),
+        }
+    }
+
+    // After a list comma, check whether the list ends here because of an allowed trailing comma.
+    fn is_trailing_comma_end(&mut self, terminators: &[Token]) -> bool {
+        self.options.allow_trailing_comma && self.current_token().is_some_and(|token| terminators.contains(token))
+    }
+
     // Parse an identifier (e.g., table or column name); error if not an identifier.
+    // Every name the parser accepts goes through here, so this is the one place max_identifier_length is checked.
     fn parse_identifier(&mut self) -> Result<String, ParseError> {
+        self.keyword_as_identifier();
         if let Some(Token::Identifier(ident)) = self.current_token() {
             let ident = ident.clone();
+            if self.options.max_identifier_length.is_some_and(|max| ident.chars().count() > max) {
+                return Err(ParseError::IdentifierTooLong(ident));
+            }
             self.advance();
             Ok(ident)
         } else {
@@ -85,21 +433,59 @@ impl Parser {
         }
     }
 
+    // When allow_keywords_as_identifiers is set, turn a current non-reserved keyword into an
+    // identifier token (lowercased), so name positions accept e.g. `key`. DATE/TIMESTAMP before
+    // a string stay keywords because they start a typed literal.
+    fn keyword_as_identifier(&mut self) {
+        self.filter_as_identifier();
+        if !self.options.allow_keywords_as_identifiers {
+            return;
+        }
+        let (name, starts_typed_literal) = match self.current_token() {
+            Some(Token::Keyword(keyword)) if !keyword.is_reserved() => {
+                (keyword.to_string().to_lowercase(), matches!(keyword, Keyword::Date | Keyword::Timestamp))
+            }
+            _ => return,
+        };
+        if starts_typed_literal && matches!(self.peek_nth(1), Some(Token::String(_))) {
+            return;
+        }
+        if let Some((token, _)) = self.lookahead.front_mut() {
+            *token = Token::Identifier(name);
+        }
+    }
+
+    // FILTER is a keyword only for the FILTER (WHERE ...) clause, so unless ( follows, turn it into
+    // the identifier `filter` regardless of allow_keywords_as_identifiers (e.g., SELECT filter FROM t).
+    fn filter_as_identifier(&mut self) {
+        if self.current_token() != Some(&Token::Keyword(Keyword::Filter))
+            || self.peek_nth(1) == Some(&Token::LeftParentheses)
+        {
+            return;
+        }
+        if let Some((token, _)) = self.lookahead.front_mut() {
+            *token = Token::Identifier("filter".to_string());
+        }
+    }
+
     // Define operator precedence for the Pratt parser.
-    fn get_precedence(&self, token: &Token) -> u8 {
+    pub(crate) fn get_precedence(&self, token: &Token) -> u8 {
         // Lower numbers mean lower precedence (e.g., OR < AND < comparisons).
+        if let Some(&(_, _, precedence, _)) = operator_entry(token) {
+            return precedence;
+        }
         match token {
-            Token::Keyword(Keyword::Or) => 10,
-            Token::Keyword(Keyword::And) => 20,
-            Token::Equal | Token::NotEqual | Token::GreaterThan | Token::GreaterThanOrEqual | Token::LessThan | Token::LessThanOrEqual => 30,
-            Token::Plus | Token::Minus => 50,
-            Token::Star | Token::Divide => 60,
+            Token::Keyword(Keyword::Is) => 25, // Below comparisons: `a = b IS TRUE` tests the comparison.
+            Token::Keyword(Keyword::In) | Token::Keyword(Keyword::Like) | Token::Keyword(Keyword::Between) => 40,
+            Token::Keyword(Keyword::Collate) => 90, // Postfix COLLATE binds tighter than unary operators.
+            Token::DoubleColon => 95, // a + b::INT casts only b.
             _ => 0, // Non-operators have no precedence.
         }
     }
 
     // Parse prefix expressions (e.g., numbers, strings, identifiers, unary ops).
     fn parse_prefix(&mut self) -> Result<Expression, ParseError> {
+        self.keyword_as_identifier();
         match self.current_token() {
             Some(Token::Number(num)) => {
                 let num = *num;
@@ -111,28 +497,106 @@ impl Parser {
                 self.advance();
                 Ok(Expression::String(s))
             }
-            Some(Token::Identifier(ident)) => {
-                let ident = ident.clone();
+            Some(Token::Identifier(_)) => {
+                // parse_identifier applies max_identifier_length to every part of the name.
+                let mut parts = vec![self.parse_identifier()?];
+                // Dots join a qualified name (e.g., users.id or math.sqrt).
+                while self.current_token() == Some(&Token::Dot) {
+                    self.advance();
+                    parts.push(self.parse_identifier()?);
+                }
+                // A name directly followed by ( is a function call; a qualified one keeps its dots.
+                if self.current_token() == Some(&Token::LeftParentheses) {
+                    self.advance();
+                    return self.parse_function_call(parts.join("."));
+                }
+                if parts.len() > 1 {
+                    return Ok(Expression::CompoundIdentifier(parts));
+                }
+                Ok(Expression::Identifier(parts.remove(0)))
+            }
+            Some(Token::Question) => {
+                self.advance();
+                Ok(Expression::Parameter(ParamKind::Anonymous))
+            }
+            Some(Token::PositionalParam(position)) => {
+                let position = *position;
+                self.advance();
+                Ok(Expression::Parameter(ParamKind::Positional(position)))
+            }
+            Some(Token::NamedParam(name)) => {
+                let name = name.clone();
                 self.advance();
-                Ok(Expression::Identifier(ident))
+                Ok(Expression::Parameter(ParamKind::Named(name)))
             }
             Some(Token::Keyword(Keyword::True)) => {
                 self.advance();
                 Ok(Expression::Bool(true))
             }
+            // Typed literal: DATE/TIMESTAMP immediately followed by a string (e.g., DATE '2020-01-01').
+            Some(Token::Keyword(keyword @ (Keyword::Date | Keyword::Timestamp))) => {
+                let ty = if *keyword == Keyword::Date { DBType::Date } else { DBType::Timestamp };
+                self.advance();
+                let value = match self.current_token() {
+                    Some(Token::String(value)) => value.clone(),
+                    other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+                };
+                // Only the shape is checked loosely; the value is not parsed as a date.
+                if value.trim().is_empty() {
+                    return Err(ParseError::InvalidTypedLiteral(ty));
+                }
+                self.advance();
+                Ok(Expression::TypedLiteral { ty, value })
+            }
             Some(Token::Keyword(Keyword::False)) => {
                 self.advance();
                 Ok(Expression::Bool(false))
             }
+            // Niladic functions, written without parentheses (e.g., DEFAULT CURRENT_TIMESTAMP).
+            Some(Token::Keyword(Keyword::CurrentDate)) => {
+                self.advance();
+                Ok(Expression::CurrentDate)
+            }
+            Some(Token::Keyword(Keyword::CurrentTimestamp)) => {
+                self.advance();
+                Ok(Expression::CurrentTimestamp)
+            }
+            // DEFAULT as a value; only meaningful inside INSERT VALUES, but accepted anywhere.
+            Some(Token::Keyword(Keyword::Default)) => {
+                self.advance();
+                Ok(Expression::Default)
+            }
             Some(Token::LeftParentheses) => {
                 self.advance();
+                // Function calls and value lists read their own parentheses, so () here is empty.
+                if self.current_token() == Some(&Token::RightParentheses) {
+                    return Err(ParseError::EmptyParentheses);
+                }
                 let expr = self.parse_expression(0)?; // Parse inside parentheses.
+                // A comma makes this a row constructor, e.g. (1, 2, 3); a single expression stays scalar.
+                if self.current_token() == Some(&Token::Comma) {
+                    self.advance();
+                    let mut items = vec![expr];
+                    items.extend(self.parse_expression_list(&[Token::RightParentheses])?);
+                    self.expect_token(Token::RightParentheses)?;
+                    return Ok(Expression::List(items));
+                }
                 self.expect_token(Token::RightParentheses)?;
                 Ok(expr)
             }
             Some(Token::Minus) => {
                 self.advance();
-                let expr = self.parse_expression(80)?; // High precedence for unary minus.
+                let expr = self.parse_expression(UnaryOperator::Minus.precedence())?;
+                // The operand is parsed first, so -1 ^ 2 still negates the whole power.
+                let folded = match expr {
+                    // i64::MIN has no positive i64 counterpart, so its magnitude is matched on its own.
+                    Expression::Number(n) if self.options.fold_unary_minus && n == i64::MIN.unsigned_abs() => Some(i64::MIN),
+                    Expression::Number(n) if self.options.fold_unary_minus => i64::try_from(n).ok().map(|n| -n),
+                    _ => None,
+                };
+                if let Some(n) = folded {
+                    return Ok(Expression::SignedNumber(n));
+                }
                 Ok(Expression::UnaryOperation {
                     operator: UnaryOperator::Minus,
                     operand: Box::new(expr),
@@ -140,7 +604,7 @@ impl Parser {
             }
             Some(Token::Plus) => {
                 self.advance();
-                let expr = self.parse_expression(80)?;
+                let expr = self.parse_expression(UnaryOperator::Plus.precedence())?;
                 Ok(Expression::UnaryOperation {
                     operator: UnaryOperator::Plus,
                     operand: Box::new(expr),
@@ -148,7 +612,7 @@ impl Parser {
             }
             Some(Token::Keyword(Keyword::Not)) => {
                 self.advance();
-                let expr = self.parse_expression(80)?;
+                let expr = self.parse_expression(UnaryOperator::Not.precedence())?;
                 Ok(Expression::UnaryOperation {
                     operator: UnaryOperator::Not,
                     operand: Box::new(expr),
@@ -158,115 +622,219 @@ impl Parser {
         }
     }
 
-    // Parse infix operators (e.g., +, -, *, =, AND) based on precedence.
-    fn parse_infix(&mut self, left: Expression, precedence: u8) -> Result<Expression, ParseError> {
+    // Parse a collation name after COLLATE; either an identifier or a string literal.
+    fn parse_collation(&mut self) -> Result<String, ParseError> {
         match self.current_token() {
-            Some(Token::Plus) => {
-                self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::Plus,
-                    right_operand: Box::new(right),
-                })
-            }
-            Some(Token::Minus) => {
-                self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::Minus,
-                    right_operand: Box::new(right),
-                })
-            }
-            Some(Token::Star) => {
+            Some(Token::Identifier(name)) | Some(Token::String(name)) => {
+                let name = name.clone();
                 self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::Multiply,
-                    right_operand: Box::new(right),
-                })
+                Ok(name)
             }
-            Some(Token::Divide) => {
+            _ => Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof))),
+        }
+    }
+
+    // Parse a function call after its name and opening parenthesis: the arguments, an optional
+    // FILTER (WHERE ...), and an optional OVER (...) that makes it a window function.
+    fn parse_function_call(&mut self, name: String) -> Result<Expression, ParseError> {
+        let (distinct, args) = self.parse_function_args()?;
+        check_argument_count(&name, args.len())?;
+        let filter = self.parse_filter(&name)?;
+        let call = Expression::FunctionCall { name, distinct, args, filter };
+        if self.consume_keyword(Keyword::Over) {
+            return self.parse_window(call);
+        }
+        Ok(call)
+    }
+
+    // Parse an optional FILTER (WHERE condition) after the arguments of an aggregate call. FILTER only
+    // starts the clause when followed by (; anywhere else it is read as an identifier (see
+    // filter_as_identifier).
+    fn parse_filter(&mut self, function: &str) -> Result<Option<Box<Expression>>, ParseError> {
+        if self.current_token() != Some(&Token::Keyword(Keyword::Filter))
+            || self.peek_nth(1) != Some(&Token::LeftParentheses)
+        {
+            return Ok(None);
+        }
+        if !AGGREGATE_FUNCTIONS.iter().any(|aggregate| aggregate.eq_ignore_ascii_case(function)) {
+            return Err(ParseError::FilterOnNonAggregate(function.to_string()));
+        }
+        self.advance();
+        self.advance();
+        self.expect_keyword(Keyword::Where)?;
+        let condition = self.parse_expression(0)?;
+        self.expect_token(Token::RightParentheses)?;
+        Ok(Some(Box::new(condition)))
+    }
+
+    // Parse the window spec after OVER: ([PARTITION BY expr, ...] [ORDER BY item, ...]).
+    fn parse_window(&mut self, func: Expression) -> Result<Expression, ParseError> {
+        self.expect_token(Token::LeftParentheses)?;
+        let mut partition_by = Vec::new();
+        if self.consume_keyword(Keyword::Partition) {
+            self.expect_keyword(Keyword::By)?;
+            partition_by = self.parse_expression_list(&[Token::Keyword(Keyword::Order), Token::RightParentheses])?;
+        }
+        let mut order_by = Vec::new();
+        if self.consume_keyword(Keyword::Order) {
+            self.expect_keyword(Keyword::By)?;
+            order_by = self.parse_order_by_list(&[Token::RightParentheses])?;
+        }
+        self.expect_token(Token::RightParentheses)?;
+        Ok(Expression::WindowFunction { func: Box::new(func), partition_by, order_by })
+    }
+
+    // Parse comma-separated function arguments after the opening parenthesis, up to and including ).
+    // Each argument is a full expression, so arguments can nest calls and operators.
+    // A leading DISTINCT (e.g., COUNT(DISTINCT country)) is reported alongside the arguments.
+    fn parse_function_args(&mut self) -> Result<(bool, Vec<Expression>), ParseError> {
+        let mut args = Vec::new();
+        let distinct = self.current_token() == Some(&Token::Keyword(Keyword::Distinct));
+        if distinct {
+            self.advance(); // DISTINCT must be followed by at least one argument.
+        } else if self.current_token() == Some(&Token::RightParentheses) {
+            self.advance(); // Empty argument list, e.g. NOW().
+            return Ok((false, args));
+        } else if self.current_token() == Some(&Token::Star) && self.peek_nth(1) == Some(&Token::RightParentheses) {
+            self.advance(); // Star argument, e.g. COUNT(*).
+            self.advance();
+            return Ok((false, vec![Expression::Wildcard]));
+        }
+        loop {
+            args.push(self.parse_expression(0)?);
+            if self.current_token() == Some(&Token::Comma) {
                 self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::Divide,
-                    right_operand: Box::new(right),
-                })
+            } else {
+                break;
             }
-            Some(Token::GreaterThan) => {
-                self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::GreaterThan,
-                    right_operand: Box::new(right),
-                })
+        }
+        self.expect_token(Token::RightParentheses)?;
+        Ok((distinct, args))
+    }
+
+    // Parse infix operators (e.g., +, -, *, =, AND) based on precedence.
+    fn parse_infix(&mut self, left: Expression, precedence: u8) -> Result<Expression, ParseError> {
+        // A comparison operator followed by ALL/ANY/SOME compares against a subquery.
+        if precedence == COMPARISON_PRECEDENCE && matches!(self.peek_nth(1), Some(Token::Keyword(Keyword::All | Keyword::Any | Keyword::Some))) {
+            return self.parse_quantified(left);
+        }
+        match self.current_token() {
+            Some(Token::Keyword(Keyword::Not)) => {
+                self.advance(); // parse_expression only lets NOT through before IN/LIKE/BETWEEN.
+                self.parse_negatable_infix(left, precedence, true)
             }
-            Some(Token::GreaterThanOrEqual) => {
-                self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::GreaterThanOrEqual,
-                    right_operand: Box::new(right),
-                })
+            Some(Token::Keyword(Keyword::In | Keyword::Like | Keyword::Between)) => {
+                self.parse_negatable_infix(left, precedence, false)
             }
-            Some(Token::LessThan) => {
+            Some(Token::Keyword(Keyword::Is)) => self.parse_is(left),
+            Some(Token::Keyword(Keyword::Collate)) => {
                 self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::LessThan,
-                    right_operand: Box::new(right),
+                let collation = self.parse_collation()?;
+                Ok(Expression::Collate {
+                    expr: Box::new(left),
+                    collation,
                 })
             }
-            Some(Token::LessThanOrEqual) => {
+            Some(Token::DoubleColon) => {
                 self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::LessThanOrEqual,
-                    right_operand: Box::new(right),
+                let data_type = self.parse_column_type()?;
+                Ok(Expression::Cast {
+                    expr: Box::new(left),
+                    data_type,
                 })
             }
-            Some(Token::Equal) => {
+            Some(token) => {
+                let Some((_, operator, _, associativity)) = operator_entry(token) else {
+                    return Err(ParseError::UnexpectedToken(token.clone()));
+                };
+                let operator = operator.clone();
                 self.advance();
-                let right = self.parse_expression(precedence)?;
+                // For a left-associative operator the right operand stops at an operator of the same
+                // precedence, so `a - b - c` groups as `(a - b) - c`; a right-associative one lets it
+                // continue. Non-associative chains are rejected in parse_expression.
+                let right_precedence = match associativity {
+                    Associativity::Right => precedence - 1,
+                    Associativity::Left | Associativity::NonAssociative => precedence,
+                };
+                let right = self.parse_expression(right_precedence)?;
                 Ok(Expression::BinaryOperation {
                     left_operand: Box::new(left),
-                    operator: BinaryOperator::Equal, // Matches Token::Equal for '='.
+                    operator,
                     right_operand: Box::new(right),
                 })
             }
-            Some(Token::NotEqual) => {
+            None => Err(ParseError::UnexpectedToken(Token::Eof)),
+        }
+    }
+
+    // Parse IN, LIKE, or BETWEEN after the left operand; negated is set when a NOT came before it.
+    fn parse_negatable_infix(&mut self, left: Expression, precedence: u8, negated: bool) -> Result<Expression, ParseError> {
+        match self.current_token() {
+            Some(Token::Keyword(Keyword::In)) => {
                 self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::NotEqual,
-                    right_operand: Box::new(right),
+                self.expect_token(Token::LeftParentheses)?;
+                // IN (SELECT ...) tests membership in a subquery's rows instead of a value list.
+                if self.consume_keyword(Keyword::Select) {
+                    let subquery = self.parse_query_body()?;
+                    self.expect_token(Token::RightParentheses)?;
+                    return Ok(Expression::InSubquery {
+                        expr: Box::new(left),
+                        subquery: Box::new(subquery),
+                        negated,
+                    });
+                }
+                let mut list = Vec::new();
+                loop {
+                    list.push(self.parse_expression(0)?);
+                    if self.current_token() == Some(&Token::Comma) {
+                        self.advance();
+                    } else {
+                        break;
+                    }
+                }
+                self.expect_token(Token::RightParentheses)?;
+                Ok(Expression::InList {
+                    expr: Box::new(left),
+                    list,
+                    negated,
                 })
             }
-            Some(Token::Keyword(Keyword::And)) => {
+            Some(Token::Keyword(Keyword::Like)) => {
                 self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::And,
-                    right_operand: Box::new(right),
+                let pattern = self.parse_expression(precedence)?;
+                // Optional ESCAPE '<char>' after the pattern.
+                let escape = if self.consume_keyword(Keyword::Escape) {
+                    let escape = match self.current_token() {
+                        Some(Token::String(s)) => s.clone(),
+                        other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+                    };
+                    let mut chars = escape.chars();
+                    let (Some(c), None) = (chars.next(), chars.next()) else {
+                        return Err(ParseError::InvalidEscape(escape));
+                    };
+                    self.advance();
+                    Some(c)
+                } else {
+                    None
+                };
+                Ok(Expression::Like {
+                    expr: Box::new(left),
+                    pattern: Box::new(pattern),
+                    negated,
+                    escape,
                 })
             }
-            Some(Token::Keyword(Keyword::Or)) => {
+            Some(Token::Keyword(Keyword::Between)) => {
                 self.advance();
-                let right = self.parse_expression(precedence)?;
-                Ok(Expression::BinaryOperation {
-                    left_operand: Box::new(left),
-                    operator: BinaryOperator::Or,
-                    right_operand: Box::new(right),
+                // Bounds are parsed above AND's precedence, so the AND here separates the bounds.
+                let low = self.parse_expression(precedence)?;
+                self.expect_keyword(Keyword::And)?;
+                let high = self.parse_expression(precedence)?;
+                Ok(Expression::Between {
+                    expr: Box::new(left),
+                    low: Box::new(low),
+                    high: Box::new(high),
+                    negated,
                 })
             }
             _ => Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof))),
@@ -275,195 +843,519 @@ impl Parser {
 
     // Parse an expression using the Pratt algorithm, handling precedence.
     fn parse_expression(&mut self, precedence: u8) -> Result<Expression, ParseError> {
+        // Every nested expression passes through here, so this bounds recursion depth.
+        if self.depth >= self.options.max_depth {
+            return Err(ParseError::DepthLimitExceeded);
+        }
+        self.depth += 1;
+        let result = self.parse_expression_inner(precedence);
+        self.depth -= 1;
+        result
+    }
+
+    // Pratt loop behind parse_expression.
+    fn parse_expression_inner(&mut self, precedence: u8) -> Result<Expression, ParseError> {
         // Start with a prefix expression (e.g., number, identifier).
         let mut left = self.parse_prefix()?;
+        // Whether `left` is an unparenthesized comparison built in this loop; a parenthesized
+        // one arrives from parse_prefix, so `(a < b) = c` is still allowed.
+        let mut left_is_comparison = false;
         // Continue parsing infix operators with higher precedence.
         while self.current_token().is_some() {
             let token = self.current_token().cloned().unwrap_or(Token::Eof);
-            let next_precedence = self.get_precedence(&token);
+            // NOT in infix position only continues the expression as NOT IN / NOT LIKE / NOT BETWEEN.
+            // Otherwise the expression ends here and the caller decides (e.g., a NOT NULL constraint).
+            let next_precedence = if token == Token::Keyword(Keyword::Not) {
+                match self.peek_nth(1) {
+                    Some(next @ Token::Keyword(Keyword::In | Keyword::Like | Keyword::Between)) => {
+                        let next = next.clone();
+                        self.get_precedence(&next)
+                    }
+                    _ => 0,
+                }
+            } else {
+                self.get_precedence(&token)
+            };
             if next_precedence <= precedence {
                 break;
             }
+            // Comparisons are non-associative (see OPERATORS); reject `a < b < c`.
+            let is_comparison = operator_entry(&token)
+                .is_some_and(|&(.., associativity)| associativity == Associativity::NonAssociative);
+            if is_comparison && left_is_comparison {
+                return Err(ParseError::ChainedComparison(token));
+            }
             left = self.parse_infix(left, next_precedence)?;
+            left_is_comparison = is_comparison;
         }
-        // Handle ASC/DESC for ORDER BY after the full expression.
-        if self.current_token() == Some(&Token::Keyword(Keyword::Asc)) {
-            self.advance();
-            return Ok(Expression::UnaryOperation {
-                operator: UnaryOperator::Asc,
-                operand: Box::new(left),
-            });
-        }
-        if self.current_token() == Some(&Token::Keyword(Keyword::Desc)) {
-            self.advance();
-            return Ok(Expression::UnaryOperation {
-                operator: UnaryOperator::Desc,
-                operand: Box::new(left),
-            });
-        }
+        // ASC/DESC are not part of expressions; ORDER BY items handle them.
         Ok(left)
     }
 
-    // Parse SELECT statements, including SELECT * for bonus points.
-    fn parse_select(&mut self) -> Result<Statement, ParseError> {
-        // Check for SELECT * (2 bonus points).
-        if self.current_token() == Some(&Token::Star) {
-            self.advance(); // Consume *.
-            self.expect_token(Token::Keyword(Keyword::From))?;
-            let from = self.parse_identifier()?;
-
-            // Parse optional WHERE clause.
-            let r#where = if self.current_token() == Some(&Token::Keyword(Keyword::Where)) {
+    // Parse `IS [NOT] NULL|TRUE|FALSE|UNKNOWN` after its left operand.
+    fn parse_is(&mut self, left: Expression) -> Result<Expression, ParseError> {
+        self.expect_keyword(Keyword::Is)?;
+        let negated = self.consume_keyword(Keyword::Not);
+        let value = match self.current_token() {
+            Some(Token::Keyword(Keyword::Null)) => {
                 self.advance();
-                Some(self.parse_expression(0)?)
-            } else {
-                None
-            };
+                return Ok(Expression::IsNull { expr: Box::new(left), negated });
+            }
+            Some(Token::Keyword(Keyword::True)) => Some(true),
+            Some(Token::Keyword(Keyword::False)) => Some(false),
+            Some(Token::Keyword(Keyword::Unknown)) => None,
+            other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+        };
+        self.advance();
+        Ok(Expression::IsBool { expr: Box::new(left), value, negated })
+    }
 
-            // Parse optional ORDER BY clause.
-            let mut orderby = Vec::new();
-            if self.current_token() == Some(&Token::Keyword(Keyword::Order)) {
+    // Parse `<comparison> ALL|ANY|SOME (SELECT ...)` after its left operand.
+    fn parse_quantified(&mut self, left: Expression) -> Result<Expression, ParseError> {
+        let op = match self.current_token() {
+            Some(Token::Equal) => BinaryOperator::Equal,
+            Some(Token::NotEqual) => BinaryOperator::NotEqual,
+            Some(Token::GreaterThan) => BinaryOperator::GreaterThan,
+            Some(Token::GreaterThanOrEqual) => BinaryOperator::GreaterThanOrEqual,
+            Some(Token::LessThan) => BinaryOperator::LessThan,
+            Some(Token::LessThanOrEqual) => BinaryOperator::LessThanOrEqual,
+            other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+        };
+        self.advance();
+        let quantifier = match self.current_token() {
+            Some(Token::Keyword(Keyword::All)) => Quantifier::All,
+            Some(Token::Keyword(Keyword::Any)) => Quantifier::Any,
+            Some(Token::Keyword(Keyword::Some)) => Quantifier::Some,
+            other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+        };
+        self.advance();
+        self.expect_token(Token::LeftParentheses)?;
+        self.expect_keyword(Keyword::Select)?;
+        let subquery = self.parse_query_body()?;
+        self.expect_token(Token::RightParentheses)?;
+        Ok(Expression::Quantified {
+            expr: Box::new(left),
+            op,
+            quantifier,
+            subquery: Box::new(subquery),
+        })
+    }
+
+    // Parse a comma-separated list of expressions (e.g., GROUP BY).
+    // A trailing comma is allowed before any of the terminators when the option is enabled.
+    fn parse_expression_list(&mut self, terminators: &[Token]) -> Result<Vec<Expression>, ParseError> {
+        let mut list = Vec::new();
+        loop {
+            list.push(self.parse_expression(0)?);
+            if self.current_token() == Some(&Token::Comma) {
                 self.advance();
-                self.expect_token(Token::Keyword(Keyword::By))?;
-                loop {
-                    let expr = self.parse_expression(0)?;
-                    orderby.push(expr);
-                    if self.current_token() == Some(&Token::Comma) {
-                        self.advance();
-                    } else {
-                        break;
-                    }
+                if self.is_trailing_comma_end(terminators) {
+                    break;
                 }
+            } else {
+                break;
             }
+        }
+        Ok(list)
+    }
 
-            // Ensure query ends with a semicolon.
-            if self.current_token() != Some(&Token::Semicolon) {
-                return Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof)));
+    // Parse the GROUP BY items: plain expressions, ROLLUP (..), CUBE (..), or GROUPING SETS (..).
+    fn parse_group_by_list(&mut self, terminators: &[Token]) -> Result<Vec<GroupByKind>, ParseError> {
+        let mut list = Vec::new();
+        loop {
+            let item = match self.current_token() {
+                Some(Token::Keyword(Keyword::Rollup)) => {
+                    self.advance();
+                    GroupByKind::Rollup(self.parse_parenthesized_expressions()?)
+                }
+                Some(Token::Keyword(Keyword::Cube)) => {
+                    self.advance();
+                    GroupByKind::Cube(self.parse_parenthesized_expressions()?)
+                }
+                Some(Token::Keyword(Keyword::Grouping)) => {
+                    self.advance();
+                    self.expect_keyword(Keyword::Sets)?;
+                    self.expect_token(Token::LeftParentheses)?;
+                    let mut sets = Vec::new();
+                    loop {
+                        // Each set is (a, b), the empty set (), or a single bare expression.
+                        if self.current_token() == Some(&Token::LeftParentheses) {
+                            self.advance();
+                            if self.current_token() == Some(&Token::RightParentheses) {
+                                self.advance();
+                                sets.push(Vec::new());
+                            } else {
+                                sets.push(self.parse_expression_list(&[Token::RightParentheses])?);
+                                self.expect_token(Token::RightParentheses)?;
+                            }
+                        } else {
+                            sets.push(vec![self.parse_expression(0)?]);
+                        }
+                        if self.current_token() != Some(&Token::Comma) {
+                            break;
+                        }
+                        self.advance();
+                    }
+                    self.expect_token(Token::RightParentheses)?;
+                    GroupByKind::GroupingSets(sets)
+                }
+                _ => GroupByKind::Expression(self.parse_expression(0)?),
+            };
+            list.push(item);
+            if self.current_token() == Some(&Token::Comma) {
+                self.advance();
+                if self.is_trailing_comma_end(terminators) {
+                    break;
+                }
+            } else {
+                break;
             }
-            self.advance();
-
-            // Return SelectAll for SELECT * queries.
-            return Ok(Statement::SelectAll {
-                from,
-                r#where,
-                orderby,
-            });
         }
+        Ok(list)
+    }
 
-        // Parse regular SELECT with column expressions.
+    // Parse `(expr, ...)` (e.g., the columns of ROLLUP).
+    fn parse_parenthesized_expressions(&mut self) -> Result<Vec<Expression>, ParseError> {
+        self.expect_token(Token::LeftParentheses)?;
+        let list = self.parse_expression_list(&[Token::RightParentheses])?;
+        self.expect_token(Token::RightParentheses)?;
+        Ok(list)
+    }
+
+    // Parse the select list: expressions with optional aliases, up to FROM.
+    // `expr AS name` is always accepted; with allow_implicit_alias, a bare identifier directly
+    // before a comma or FROM (e.g., `age years FROM`) is taken as the alias too.
+    fn parse_select_list(&mut self) -> Result<Vec<Expression>, ParseError> {
         let mut columns = Vec::new();
-        if self.current_token() == Some(&Token::Keyword(Keyword::From)) {
-            return Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::From))); // Require at least one column.
+        loop {
+            let expr = self.parse_expression(0)?;
+            let explicit_alias = self.consume_keyword(Keyword::As);
+            self.filter_as_identifier();
+            let implicit_alias = !explicit_alias
+                && self.options.allow_implicit_alias
+                && matches!(self.current_token(), Some(Token::Identifier(_)))
+                && matches!(self.peek_nth(1), Some(Token::Comma | Token::Keyword(Keyword::From)));
+            let alias = if explicit_alias || implicit_alias {
+                Some(self.parse_identifier()?)
+            } else {
+                None
+            };
+            columns.push(match alias {
+                Some(alias) => Expression::Alias { expr: Box::new(expr), alias },
+                None => expr,
+            });
+            if self.current_token() == Some(&Token::Comma) {
+                self.advance();
+                if self.is_trailing_comma_end(&[Token::Keyword(Keyword::From)]) {
+                    break;
+                }
+            } else {
+                break;
+            }
         }
+        Ok(columns)
+    }
 
-        // Parse comma-separated column expressions.
+    // Parse ORDER BY items: each is a full expression followed by an optional ASC/DESC.
+    fn parse_order_by_list(&mut self, terminators: &[Token]) -> Result<Vec<OrderByItem>, ParseError> {
+        let mut items = Vec::new();
         loop {
             let expr = self.parse_expression(0)?;
-            columns.push(expr);
+            let order = match self.current_token() {
+                Some(Token::Keyword(Keyword::Asc)) => Some(SortOrder::Asc),
+                Some(Token::Keyword(Keyword::Desc)) => Some(SortOrder::Desc),
+                _ => None,
+            };
+            if order.is_some() {
+                self.advance();
+            }
+            items.push(OrderByItem { expr, order });
             if self.current_token() == Some(&Token::Comma) {
                 self.advance();
+                if self.is_trailing_comma_end(terminators) {
+                    break;
+                }
             } else {
                 break;
             }
         }
+        Ok(items)
+    }
 
-        // Expect FROM clause.
-        self.expect_token(Token::Keyword(Keyword::From))?;
-        let from = self.parse_identifier()?;
-
-        // Parse optional WHERE clause.
-        let r#where = if self.current_token() == Some(&Token::Keyword(Keyword::Where)) {
+    // Parse a non-negative integer literal (e.g., a LIMIT count).
+    fn parse_number(&mut self) -> Result<u64, ParseError> {
+        if let Some(Token::Number(num)) = self.current_token() {
+            let num = *num;
             self.advance();
-            Some(self.parse_expression(0)?)
+            Ok(num)
         } else {
-            None
-        };
+            Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof)))
+        }
+    }
+
+    // Parse the optional clauses after FROM, in canonical SQL order:
+    // WHERE, GROUP BY, HAVING, ORDER BY, LIMIT, OFFSET.
+    // The SQL-standard `OFFSET n ROWS FETCH NEXT m ROWS ONLY` fills the same limit/offset fields.
+    // Each step only looks at the current token, so a clause that appears out of order is left
+    // unconsumed and rejected by the statement terminator check.
+    fn parse_select_clauses(&mut self) -> Result<SelectClauses, ParseError> {
+        let mut clauses = SelectClauses::default();
+        // Tokens that may follow a trailing comma in ORDER BY / GROUP BY: the end of the statement
+        // (the configured delimiter), of a subquery, or a later clause.
+        let order_by_end = [
+            self.options.delimiter.clone(),
+            Token::RightParentheses,
+            Token::Keyword(Keyword::Union),
+            Token::Keyword(Keyword::Limit),
+            Token::Keyword(Keyword::Offset),
+            Token::Keyword(Keyword::Fetch),
+        ];
+        let group_by_end = [
+            self.options.delimiter.clone(),
+            Token::RightParentheses,
+            Token::Keyword(Keyword::Having),
+            Token::Keyword(Keyword::Order),
+            Token::Keyword(Keyword::Union),
+            Token::Keyword(Keyword::Limit),
+            Token::Keyword(Keyword::Offset),
+            Token::Keyword(Keyword::Fetch),
+        ];
+
+        // Parse optional WHERE clause. Any expression is accepted as the predicate, including a bare
+        // BOOL column (WHERE active); whether it is really boolean needs the schema, so it is not checked.
+        if self.consume_keyword(Keyword::Where) {
+            clauses.r#where = Some(self.parse_expression(0)?);
+        }
+
+        // Parse optional GROUP BY clause.
+        if self.consume_keyword(Keyword::Group) {
+            self.expect_keyword(Keyword::By)?;
+            clauses.group_by = self.parse_group_by_list(&group_by_end)?;
+        }
+
+        // Parse optional HAVING clause.
+        if self.consume_keyword(Keyword::Having) {
+            clauses.having = Some(self.parse_expression(0)?);
+        }
 
         // Parse optional ORDER BY clause.
-        let mut orderby = Vec::new();
-        if self.current_token() == Some(&Token::Keyword(Keyword::Order)) {
-            self.advance();
-            self.expect_token(Token::Keyword(Keyword::By))?;
-            loop {
-                let expr = self.parse_expression(0)?;
-                orderby.push(expr);
-                if self.current_token() == Some(&Token::Comma) {
-                    self.advance();
-                } else {
-                    break;
-                }
-            }
+        if self.consume_keyword(Keyword::Order) {
+            self.expect_keyword(Keyword::By)?;
+            clauses.orderby = self.parse_order_by_list(&order_by_end)?;
+        }
+
+        // Parse optional LIMIT clause (a count, or ALL for no limit).
+        if self.consume_keyword(Keyword::Limit) {
+            clauses.limit = if self.consume_keyword(Keyword::All) {
+                Some(Limit::All)
+            } else {
+                Some(Limit::Count(self.parse_number()?))
+            };
+        }
+
+        // Parse optional OFFSET clause; ROWS after the count is the SQL-standard spelling.
+        if self.consume_keyword(Keyword::Offset) {
+            clauses.offset = Some(self.parse_number()?);
+            self.consume_keyword(Keyword::Rows);
         }
 
+        // Parse optional FETCH NEXT n ROWS ONLY, the SQL-standard alternative to LIMIT.
+        if clauses.limit.is_none() && self.consume_keyword(Keyword::Fetch) {
+            self.expect_keyword(Keyword::Next)?;
+            clauses.limit = Some(Limit::Count(self.parse_number()?));
+            self.expect_keyword(Keyword::Rows)?;
+            self.expect_keyword(Keyword::Only)?;
+        }
+
+        Ok(clauses)
+    }
+
+    // Parse SELECT statements, including SELECT * for bonus points.
+    fn parse_select(&mut self) -> Result<Statement, ParseError> {
+        let statement = self.parse_query_body()?;
         // Ensure query ends with a semicolon.
-        if self.current_token() != Some(&Token::Semicolon) {
-            return Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof)));
+        self.expect_statement_end()?;
+        Ok(statement)
+    }
+
+    // Parse a SELECT after the SELECT keyword plus any `UNION [ALL] SELECT ...` that follows,
+    // without the terminator (shared with subqueries). UNION chains are left-associative.
+    fn parse_query_body(&mut self) -> Result<Statement, ParseError> {
+        let mut query = self.parse_select_body()?;
+        while self.consume_keyword(Keyword::Union) {
+            let all = self.consume_keyword(Keyword::All);
+            self.expect_keyword(Keyword::Select)?;
+            let right = self.parse_select_body()?;
+            query = Statement::Union { left: Box::new(query), right: Box::new(right), all };
+        }
+        Ok(query)
+    }
+
+    // Parse the optional INTO new_table between the select list and FROM (SQL Server/PostgreSQL).
+    fn parse_select_into(&mut self) -> Result<Option<String>, ParseError> {
+        if self.consume_keyword(Keyword::Into) {
+            Ok(Some(self.parse_identifier()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Parse the FROM target: a table name, or a table-valued function call with an optional
+    // [AS] alias (e.g., generate_series(1, 10) AS s).
+    fn parse_table_source(&mut self) -> Result<TableSource, ParseError> {
+        let name = self.parse_identifier()?;
+        if self.current_token() != Some(&Token::LeftParentheses) {
+            return Ok(TableSource::Table(name));
         }
         self.advance();
+        let args = if self.current_token() == Some(&Token::RightParentheses) {
+            Vec::new()
+        } else {
+            self.parse_expression_list(&[Token::RightParentheses])?
+        };
+        self.expect_token(Token::RightParentheses)?;
+        let alias = if self.consume_keyword(Keyword::As) || matches!(self.current_token(), Some(Token::Identifier(_))) {
+            Some(self.parse_identifier()?)
+        } else {
+            None
+        };
+        Ok(TableSource::Function { name, args, alias })
+    }
+
+    // Parse a single SELECT after the SELECT keyword, without its terminator.
+    fn parse_select_body(&mut self) -> Result<Statement, ParseError> {
+        // Check for SELECT * (2 bonus points).
+        if self.current_token() == Some(&Token::Star) {
+            self.advance(); // Consume *.
+            let into = self.parse_select_into()?;
+            self.expect_keyword(Keyword::From)?;
+            let from = self.parse_table_source()?;
+            let clauses = self.parse_select_clauses()?;
+
+            // Return SelectAll for SELECT * queries.
+            return Ok(Statement::SelectAll {
+                into,
+                from,
+                r#where: clauses.r#where,
+                group_by: clauses.group_by,
+                having: clauses.having,
+                orderby: clauses.orderby,
+                limit: clauses.limit,
+                offset: clauses.offset,
+            });
+        }
+
+        // Parse regular SELECT with column expressions.
+        if self.current_token() == Some(&Token::Keyword(Keyword::From)) {
+            return Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::From))); // Require at least one column.
+        }
+
+        // Parse comma-separated column expressions.
+        let columns = self.parse_select_list()?;
+        let into = self.parse_select_into()?;
+
+        // Expect FROM clause.
+        self.expect_keyword(Keyword::From)?;
+        let from = self.parse_table_source()?;
+        let clauses = self.parse_select_clauses()?;
 
         // Return Select statement for regular SELECT queries.
         Ok(Statement::Select {
             columns,
+            into,
             from,
-            r#where,
-            orderby,
+            r#where: clauses.r#where,
+            group_by: clauses.group_by,
+            having: clauses.having,
+            orderby: clauses.orderby,
+            limit: clauses.limit,
+            offset: clauses.offset,
         })
     }
 
+    // Parse a column type (INT, BOOL, VARCHAR(n), DATE, TIMESTAMP), as in CREATE TABLE or a :: cast.
+    fn parse_column_type(&mut self) -> Result<DBType, ParseError> {
+        let column_type = match self.current_token() {
+            Some(Token::Keyword(Keyword::Int)) => DBType::Int,
+            Some(Token::Keyword(Keyword::Bool)) => DBType::Bool,
+            Some(Token::Keyword(Keyword::Date)) => DBType::Date,
+            Some(Token::Keyword(Keyword::Timestamp)) => DBType::Timestamp,
+            Some(Token::Keyword(Keyword::Varchar)) => {
+                self.advance();
+                self.expect_token(Token::LeftParentheses)?;
+                let len = match self.current_token() {
+                    Some(Token::Number(len)) => {
+                        let len_val = *len;
+                        // Validate VARCHAR length (1 to 65535).
+                        if len_val == 0 || len_val > 65535 {
+                            return Err(ParseError::InvalidVarcharLength);
+                        }
+                        len_val
+                    }
+                    _ => return Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof))),
+                };
+                self.advance();
+                self.expect_token(Token::RightParentheses)?;
+                return Ok(DBType::Varchar(len as usize));
+            }
+            _ => return Err(ParseError::InvalidColumnType),
+        };
+        self.advance();
+        Ok(column_type)
+    }
+
     // Parse CREATE TABLE statements with column types and constraints.
     fn parse_create_table(&mut self) -> Result<Statement, ParseError> {
-        self.expect_token(Token::Keyword(Keyword::Table))?;
+        self.expect_keyword(Keyword::Table)?;
+        // Parse optional IF NOT EXISTS.
+        let if_not_exists = if self.consume_keyword(Keyword::If) {
+            self.expect_keyword(Keyword::Not)?;
+            self.expect_keyword(Keyword::Exists)?;
+            true
+        } else {
+            false
+        };
         let table_name = self.parse_identifier()?;
+
+        // CREATE TABLE name AS SELECT ... takes its columns from the query.
+        if self.consume_keyword(Keyword::As) {
+            self.expect_keyword(Keyword::Select)?;
+            let query = self.parse_query_body()?;
+            self.expect_statement_end()?;
+            return Ok(Statement::CreateTableAs { name: table_name, query: Box::new(query) });
+        }
         self.expect_token(Token::LeftParentheses)?;
 
         // Parse comma-separated column definitions.
         let mut column_list = Vec::new();
         loop {
+            let start = self.current_span().start; // Column definition starts at its name.
             let column_name = self.parse_identifier()?;
 
-            // Parse column type (INT, BOOL, VARCHAR).
-            let column_type = match self.current_token() {
-                Some(Token::Keyword(Keyword::Int)) => {
-                    self.advance();
-                    DBType::Int
-                }
-                Some(Token::Keyword(Keyword::Bool)) => {
-                    self.advance();
-                    DBType::Bool
-                }
-                Some(Token::Keyword(Keyword::Varchar)) => {
-                    self.advance();
-                    self.expect_token(Token::LeftParentheses)?;
-                    let len = match self.current_token() {
-                        Some(Token::Number(len)) => {
-                            let len_val = *len;
-                            // Validate VARCHAR length (1 to 65535).
-                            if len_val == 0 || len_val > 65535 {
-                                return Err(ParseError::InvalidVarcharLength);
-                            }
-                            len_val
-                        }
-                        _ => return Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof))),
-                    };
-                    self.advance();
-                    self.expect_token(Token::RightParentheses)?;
-                    DBType::Varchar(len as usize)
-                }
-                _ => return Err(ParseError::InvalidColumnType),
-            };
+            let column_type = self.parse_column_type()?;
 
-            // Parse optional constraints (PRIMARY KEY, NOT NULL, CHECK).
+            // Parse optional constraints (PRIMARY KEY, NOT NULL, CHECK, DEFAULT, AUTO_INCREMENT, REFERENCES,
+            // GENERATED), collation, and comment.
             let mut constraints = Vec::new();
+            let mut collation = None;
+            let mut comment = None;
             while let Some(token) = self.current_token() {
                 match token {
+                    Token::Keyword(Keyword::Collate) => {
+                        if collation.is_some() {
+                            return Err(ParseError::DuplicateCollation(column_name));
+                        }
+                        self.advance();
+                        collation = Some(self.parse_collation()?);
+                    }
                     Token::Keyword(Keyword::Primary) => {
                         self.advance();
-                        self.expect_token(Token::Keyword(Keyword::Key))?;
+                        self.expect_keyword(Keyword::Key)?;
                         constraints.push(Constraint::PrimaryKey);
                     }
                     Token::Keyword(Keyword::Not) => {
                         self.advance();
-                        self.expect_token(Token::Keyword(Keyword::Null))?;
+                        self.expect_keyword(Keyword::Null)?;
                         constraints.push(Constraint::NotNull);
                     }
                     Token::Keyword(Keyword::Check) => {
@@ -473,20 +1365,64 @@ impl Parser {
                         self.expect_token(Token::RightParentheses)?;
                         constraints.push(Constraint::Check(expr));
                     }
+                    Token::Keyword(Keyword::Default) => {
+                        self.advance();
+                        // The expression stops at the next constraint keyword (e.g., NOT in NOT NULL),
+                        // since keywords like NOT have no infix precedence; the loop then continues.
+                        let expr = self.parse_expression(0)?;
+                        constraints.push(Constraint::Default(expr));
+                    }
+                    Token::Keyword(Keyword::AutoIncrement) => {
+                        self.advance();
+                        constraints.push(Constraint::AutoIncrement);
+                    }
+                    Token::Keyword(Keyword::References) => {
+                        self.advance();
+                        constraints.push(self.parse_references()?);
+                    }
+                    // GENERATED ALWAYS AS (expr) [STORED | VIRTUAL]; VIRTUAL is the default.
+                    Token::Keyword(Keyword::Generated) => {
+                        self.advance();
+                        self.expect_keyword(Keyword::Always)?;
+                        self.expect_keyword(Keyword::As)?;
+                        self.expect_token(Token::LeftParentheses)?;
+                        let expr = self.parse_expression(0)?;
+                        self.expect_token(Token::RightParentheses)?;
+                        let stored = self.consume_keyword(Keyword::Stored);
+                        if !stored {
+                            self.consume_keyword(Keyword::Virtual);
+                        }
+                        constraints.push(Constraint::Generated { expr, stored });
+                    }
+                    Token::Keyword(Keyword::Comment) => {
+                        self.advance();
+                        let text = match self.current_token() {
+                            Some(Token::String(s)) => s.clone(),
+                            other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+                        };
+                        self.advance();
+                        comment = Some(text);
+                    }
                     _ => break,
                 }
             }
 
-            // Add column to the list.
+            // Add column to the list; it ends at the last token of its type or constraints.
             column_list.push(TableColumn {
                 column_name,
                 column_type,
+                collation,
                 constraints,
+                comment,
+                span: Span { start, end: self.last_span.end },
             });
 
             // Continue if more columns (comma); otherwise, break.
             if self.current_token() == Some(&Token::Comma) {
                 self.advance();
+                if self.is_trailing_comma_end(&[Token::RightParentheses]) {
+                    break;
+                }
             } else {
                 break;
             }
@@ -494,30 +1430,333 @@ impl Parser {
 
         // Expect closing parenthesis and semicolon.
         self.expect_token(Token::RightParentheses)?;
-        if self.current_token() != Some(&Token::Semicolon) {
-            return Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof)));
-        }
-        self.advance();
+        self.expect_statement_end()?;
 
         // Return CreateTable statement.
         Ok(Statement::CreateTable {
             table_name,
+            if_not_exists,
             column_list,
         })
     }
 
+    // Parse the rest of a REFERENCES constraint: table [(column)] [ON DELETE action] [ON UPDATE action].
+    fn parse_references(&mut self) -> Result<Constraint, ParseError> {
+        let table = self.parse_identifier()?;
+        let column = if self.current_token() == Some(&Token::LeftParentheses) {
+            self.advance();
+            let column = self.parse_identifier()?;
+            self.expect_token(Token::RightParentheses)?;
+            Some(column)
+        } else {
+            None
+        };
+        let mut referential_actions = ReferentialActions::default();
+        while self.consume_keyword(Keyword::On) {
+            // Each of ON DELETE and ON UPDATE may appear once, in either order.
+            let slot = match self.current_token() {
+                Some(Token::Keyword(Keyword::Delete)) if referential_actions.on_delete.is_none() => {
+                    &mut referential_actions.on_delete
+                }
+                Some(Token::Keyword(Keyword::Update)) if referential_actions.on_update.is_none() => {
+                    &mut referential_actions.on_update
+                }
+                other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+            };
+            self.advance();
+            let action = match self.current_token() {
+                Some(Token::Keyword(Keyword::Cascade)) => ReferentialAction::Cascade,
+                Some(Token::Keyword(Keyword::Restrict)) => ReferentialAction::Restrict,
+                Some(Token::Keyword(Keyword::Set)) => {
+                    self.advance();
+                    match self.current_token() {
+                        Some(Token::Keyword(Keyword::Null)) => ReferentialAction::SetNull,
+                        Some(Token::Keyword(Keyword::Default)) => ReferentialAction::SetDefault,
+                        other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+                    }
+                }
+                other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+            };
+            self.advance();
+            *slot = Some(action);
+        }
+        Ok(Constraint::References { table, column, referential_actions })
+    }
+
     // Entry point for parsing: dispatch to SELECT or CREATE TABLE.
     pub fn parse(&mut self) -> Result<Statement, ParseError> {
+        self.check_delimiter()?;
         match self.current_token() {
             Some(Token::Keyword(Keyword::Select)) => {
                 self.advance();
                 self.parse_select()
             }
+            Some(Token::Keyword(Keyword::With)) => {
+                self.advance();
+                self.parse_with()
+            }
+            Some(Token::Keyword(Keyword::Update)) => {
+                self.advance();
+                self.parse_update()
+            }
+            Some(Token::Keyword(Keyword::Insert)) => {
+                self.advance();
+                self.parse_insert()
+            }
+            Some(Token::Keyword(Keyword::Delete)) => {
+                self.advance();
+                self.parse_delete()
+            }
+            // Standalone VALUES (1, 'a'), (2, 'b');
+            Some(Token::Keyword(Keyword::Values)) => {
+                self.advance();
+                let rows = self.parse_values_rows()?;
+                self.expect_statement_end()?;
+                Ok(Statement::Values(rows))
+            }
+            // EXPLAIN [ANALYZE] <statement>; the inner statement consumes the delimiter.
+            Some(Token::Keyword(Keyword::Explain)) => {
+                self.advance();
+                let analyze = self.consume_keyword(Keyword::Analyze);
+                // EXPLAIN EXPLAIN ... recurses like a nested expression, so it counts toward max_depth.
+                if self.depth >= self.options.max_depth {
+                    return Err(ParseError::DepthLimitExceeded);
+                }
+                self.depth += 1;
+                let inner = self.parse();
+                self.depth -= 1;
+                Ok(Statement::Explain { analyze, inner: Box::new(inner?) })
+            }
+            // DESCRIBE <table>;
+            Some(Token::Keyword(Keyword::Describe)) => {
+                self.advance();
+                let table = self.parse_identifier()?;
+                self.expect_statement_end()?;
+                Ok(Statement::Describe { table })
+            }
+            // Look past CREATE to make sure a TABLE keyword follows before committing to CREATE TABLE.
             Some(Token::Keyword(Keyword::Create)) => {
+                if self.peek_nth(1) != Some(&Token::Keyword(Keyword::Table)) {
+                    return Err(ParseError::UnexpectedToken(self.peek_nth(1).cloned().unwrap_or(Token::Eof)));
+                }
                 self.advance();
                 self.parse_create_table()
             }
             _ => Err(ParseError::UnexpectedToken(self.current_token().cloned().unwrap_or(Token::Eof))),
         }
     }
+
+    // Parse UPDATE table SET col = expr[, ...] [WHERE condition];
+    // Assigned values are full expressions, so `SET count = count + 1` works.
+    fn parse_update(&mut self) -> Result<Statement, ParseError> {
+        let table_name = self.parse_identifier()?;
+        self.expect_keyword(Keyword::Set)?;
+        let assignments = self.parse_assignments()?;
+        let r#where = self.parse_optional_where()?;
+        let returning = self.parse_returning()?;
+        self.expect_statement_end()?;
+        Ok(Statement::Update { table_name, assignments, r#where, returning })
+    }
+
+    // Parse a comma-separated list of column = expression items (the SET list of UPDATE or an upsert).
+    fn parse_assignments(&mut self) -> Result<Vec<Assignment>, ParseError> {
+        let mut assignments = Vec::new();
+        loop {
+            let column = self.parse_identifier()?;
+            self.expect_token(Token::Equal)?;
+            let value = self.parse_expression(0)?;
+            assignments.push(Assignment { column, value });
+            if self.current_token() == Some(&Token::Comma) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        Ok(assignments)
+    }
+
+    // Parse INSERT INTO table [(col, ...)] followed by VALUES (...)[, ...], DEFAULT VALUES, or a SELECT.
+    fn parse_insert(&mut self) -> Result<Statement, ParseError> {
+        self.expect_keyword(Keyword::Into)?;
+        let table_name = self.parse_identifier()?;
+
+        // Parse optional target column list.
+        let mut columns = Vec::new();
+        if self.current_token() == Some(&Token::LeftParentheses) {
+            self.advance();
+            loop {
+                columns.push(self.parse_identifier()?);
+                if self.current_token() == Some(&Token::Comma) {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+            self.expect_token(Token::RightParentheses)?;
+        }
+
+        // Branch on the row source.
+        let source = match self.current_token() {
+            Some(Token::Keyword(Keyword::Values)) => {
+                self.advance();
+                InsertSource::Values(self.parse_values_rows()?)
+            }
+            Some(Token::Keyword(Keyword::Default)) => {
+                self.advance();
+                self.expect_keyword(Keyword::Values)?;
+                InsertSource::DefaultValues
+            }
+            Some(Token::Keyword(Keyword::Select)) => {
+                self.advance();
+                InsertSource::Query(Box::new(self.parse_query_body()?))
+            }
+            other => return Err(ParseError::UnexpectedToken(other.cloned().unwrap_or(Token::Eof))),
+        };
+        let on_conflict = self.parse_on_conflict()?;
+        let returning = self.parse_returning()?;
+        self.expect_statement_end()?;
+        Ok(Statement::Insert { table_name, columns, source, on_conflict, returning })
+    }
+
+    // Parse an optional ON CONFLICT [(col, ...)] DO NOTHING | DO UPDATE SET ..., or the MySQL form
+    // ON DUPLICATE KEY UPDATE ....
+    fn parse_on_conflict(&mut self) -> Result<Option<OnConflict>, ParseError> {
+        if !self.consume_keyword(Keyword::On) {
+            return Ok(None);
+        }
+        if self.consume_keyword(Keyword::Duplicate) {
+            self.expect_keyword(Keyword::Key)?;
+            self.expect_keyword(Keyword::Update)?;
+            let assignments = self.parse_assignments()?;
+            return Ok(Some(OnConflict { target: Vec::new(), action: ConflictAction::DuplicateKeyUpdate(assignments) }));
+        }
+        self.expect_keyword(Keyword::Conflict)?;
+
+        // Parse optional conflict target column list.
+        let mut target = Vec::new();
+        if self.current_token() == Some(&Token::LeftParentheses) {
+            self.advance();
+            loop {
+                target.push(self.parse_identifier()?);
+                if self.current_token() == Some(&Token::Comma) {
+                    self.advance();
+                } else {
+                    break;
+                }
+            }
+            self.expect_token(Token::RightParentheses)?;
+        }
+
+        self.expect_keyword(Keyword::Do)?;
+        let action = if self.consume_keyword(Keyword::Nothing) {
+            ConflictAction::DoNothing
+        } else {
+            self.expect_keyword(Keyword::Update)?;
+            self.expect_keyword(Keyword::Set)?;
+            ConflictAction::DoUpdate(self.parse_assignments()?)
+        };
+        Ok(Some(OnConflict { target, action }))
+    }
+
+    // Parse the comma-separated row tuples after VALUES, e.g. (1, 'a'), (2, 'b').
+    fn parse_values_rows(&mut self) -> Result<Vec<Vec<Expression>>, ParseError> {
+        let mut rows = Vec::new();
+        loop {
+            self.expect_token(Token::LeftParentheses)?;
+            rows.push(self.parse_expression_list(&[Token::RightParentheses])?);
+            self.expect_token(Token::RightParentheses)?;
+            if self.current_token() == Some(&Token::Comma) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        Ok(rows)
+    }
+
+    // Parse DELETE FROM table [WHERE condition] [RETURNING ...];
+    fn parse_delete(&mut self) -> Result<Statement, ParseError> {
+        self.expect_keyword(Keyword::From)?;
+        let table_name = self.parse_identifier()?;
+        let r#where = self.parse_optional_where()?;
+        let returning = self.parse_returning()?;
+        self.expect_statement_end()?;
+        Ok(Statement::Delete { table_name, r#where, returning })
+    }
+
+    // Parse an optional WHERE condition (UPDATE and DELETE).
+    fn parse_optional_where(&mut self) -> Result<Option<Expression>, ParseError> {
+        if self.consume_keyword(Keyword::Where) {
+            Ok(Some(self.parse_expression(0)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    // Parse an optional RETURNING list; `RETURNING *` is a single Expression::Wildcard.
+    fn parse_returning(&mut self) -> Result<Vec<Expression>, ParseError> {
+        if !self.consume_keyword(Keyword::Returning) {
+            return Ok(Vec::new());
+        }
+        if self.current_token() == Some(&Token::Star) {
+            self.advance();
+            return Ok(vec![Expression::Wildcard]);
+        }
+        let delimiter = self.options.delimiter.clone();
+        self.parse_expression_list(&[delimiter])
+    }
+
+    // Parse WITH name AS (SELECT ...)[, ...] followed by the main SELECT.
+    fn parse_with(&mut self) -> Result<Statement, ParseError> {
+        let mut ctes = Vec::new();
+        loop {
+            let name = self.parse_identifier()?;
+            self.expect_keyword(Keyword::As)?;
+            self.expect_token(Token::LeftParentheses)?;
+            self.expect_keyword(Keyword::Select)?;
+            let query = self.parse_query_body()?;
+            self.expect_token(Token::RightParentheses)?;
+            ctes.push(Cte { name, query: Box::new(query) });
+            if self.current_token() == Some(&Token::Comma) {
+                self.advance();
+            } else {
+                break;
+            }
+        }
+        self.expect_keyword(Keyword::Select)?;
+        let body = self.parse_select()?;
+        Ok(Statement::WithQuery { ctes, body: Box::new(body) })
+    }
+
+    // Like parse, but also return the span from the statement's first keyword to its semicolon,
+    // e.g. for editor integrations that highlight whole statements.
+    pub fn parse_spanned(&mut self) -> Result<Spanned<Statement>, ParseError> {
+        let start = self.current_span();
+        let node = self.parse()?;
+        Ok(Spanned { node, span: start.merge(self.last_span) })
+    }
+
+    // Parse a single standalone expression (e.g., `1 + 2 * 3` or a CHECK condition), without any
+    // statement around it. An optional delimiter may follow; anything else before the end is an error.
+    pub fn parse_expression_only(&mut self) -> Result<Expression, ParseError> {
+        self.check_delimiter()?;
+        let expr = self.parse_expression(0)?;
+        let delimiter = self.options.delimiter.clone();
+        if self.current_token() == Some(&delimiter) {
+            self.advance();
+        }
+        match self.current_token() {
+            Some(Token::Eof) | None => Ok(expr),
+            Some(token) => Err(ParseError::UnexpectedToken(token.clone())),
+        }
+    }
+
+    // Like parse, but a final statement may omit its semicolon and end at the end of input instead.
+    // Convenient for interactive input; parse itself stays strict.
+    pub fn parse_lenient(&mut self) -> Result<Statement, ParseError> {
+        self.eof_terminates = true;
+        let result = self.parse();
+        self.eof_terminates = false;
+        result
+    }
 }
\ No newline at end of file
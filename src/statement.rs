@@ -6,32 +6,131 @@
 
 // Import fmt for displaying AST structures as strings.
 use std::fmt;
+// Import Span to record where constructs appear in the source text.
+use crate::token::Span;
+// Import the comparison precedence shared with the parser.
+use crate::parser::COMPARISON_PRECEDENCE;
+// Import the formatter shared by to_sql and the Display impls.
+use crate::format::{FormatOptions, SqlFormatter};
 
 // Define Expression enum for SQL expressions (e.g., numbers, strings, operations).
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Expression {
     Number(u64), // Integer values.
+    SignedNumber(i64), // Negative integer folded from a leading minus (ParserOptions::fold_unary_minus).
     String(String), // String literals.
     Bool(bool), // Boolean values.
     Identifier(String), // Column or table names.
+    CompoundIdentifier(Vec<String>), // Qualified name, one entry per part (e.g., users.id).
     BinaryOperation { // Binary operations (e.g., a + b).
         left_operand: Box<Expression>,
         operator: BinaryOperator,
         right_operand: Box<Expression>,
     },
-    UnaryOperation { // Unary operations (e.g., -x, ASC).
+    UnaryOperation { // Unary operations (e.g., -x, NOT a).
         operator: UnaryOperator,
         operand: Box<Expression>,
     },
+    Parameter(ParamKind), // Bind parameter placeholder (e.g., ?, $1).
+    FunctionCall { // Function call (e.g., UPPER(name), COUNT(DISTINCT country)).
+        name: String,
+        distinct: bool, // DISTINCT before the arguments (aggregates).
+        args: Vec<Expression>,
+        filter: Option<Box<Expression>>, // Optional FILTER (WHERE ...) condition (aggregates).
+    },
+    Collate { // Expression with an explicit collation (e.g., name COLLATE "en_US").
+        expr: Box<Expression>,
+        collation: String,
+    },
+    Cast { // Type conversion with the :: shorthand (e.g., age::VARCHAR(10)).
+        expr: Box<Expression>,
+        data_type: DBType,
+    },
+    InList { // Membership test (e.g., a [NOT] IN (1, 2, 3)).
+        expr: Box<Expression>,
+        list: Vec<Expression>,
+        negated: bool,
+    },
+    InSubquery { // Membership in a subquery's rows (e.g., id [NOT] IN (SELECT user_id FROM orders)).
+        expr: Box<Expression>,
+        subquery: Box<Statement>,
+        negated: bool,
+    },
+    Like { // Pattern match (e.g., name [NOT] LIKE 'A%' [ESCAPE '!']).
+        expr: Box<Expression>,
+        pattern: Box<Expression>,
+        negated: bool,
+        escape: Option<char>, // Character that makes the next % or _ literal.
+    },
+    IsNull { // Null test (e.g., email IS [NOT] NULL).
+        expr: Box<Expression>,
+        negated: bool,
+    },
+    IsBool { // Truth test (e.g., active IS [NOT] TRUE).
+        expr: Box<Expression>,
+        value: Option<bool>, // TRUE/FALSE, or None for UNKNOWN.
+        negated: bool,
+    },
+    Between { // Range check (e.g., age [NOT] BETWEEN 18 AND 65).
+        expr: Box<Expression>,
+        low: Box<Expression>,
+        high: Box<Expression>,
+        negated: bool,
+    },
+    Quantified { // Comparison against every/any subquery row (e.g., price > ALL (SELECT ...)).
+        expr: Box<Expression>,
+        op: BinaryOperator,
+        quantifier: Quantifier,
+        subquery: Box<Statement>,
+    },
+    Wildcard, // * in a RETURNING list or as the argument of COUNT(*).
+    List(Vec<Expression>), // Parenthesized row constructor with two or more items (e.g., (1, 2)).
+    Alias { // Select-list item with a name (e.g., age AS years, or age years).
+        expr: Box<Expression>,
+        alias: String,
+    },
+    TypedLiteral { // Literal with an explicit type (e.g., DATE '2020-01-01').
+        ty: DBType,
+        value: String,
+    },
+    WindowFunction { // Function evaluated over a window of rows (e.g., ROW_NUMBER() OVER (PARTITION BY dept ORDER BY salary DESC)).
+        func: Box<Expression>, // The function call.
+        partition_by: Vec<Expression>, // PARTITION BY keys; empty means one partition.
+        order_by: Vec<OrderByItem>, // ORDER BY within each partition.
+    },
+    CurrentDate, // CURRENT_DATE: today's date.
+    CurrentTimestamp, // CURRENT_TIMESTAMP: the current date and time.
+    Default, // DEFAULT as a value (e.g., VALUES (DEFAULT, 5)): use the column's default.
+}
+
+// Define Quantifier enum for ALL/ANY/SOME comparisons.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Quantifier {
+    All, // True if the comparison holds for every row.
+    Any, // True if the comparison holds for at least one row.
+    Some, // Synonym for ANY, kept to preserve the original spelling.
+}
+
+// Define ParamKind enum for the supported bind parameter styles.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ParamKind {
+    Anonymous, // ? placeholder, bound by order of appearance.
+    Positional(u32), // $n placeholder, bound by explicit position.
+    Named(String), // :name or @name placeholder, stored with its prefix.
 }
 
 // Define BinaryOperator enum for binary operations.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinaryOperator {
     Plus, // Addition (+).
     Minus, // Subtraction (-).
     Multiply, // Multiplication (*).
     Divide, // Division (/).
+    Power, // Exponentiation (^).
     Equal, // Equality (=), matches Token::Equal.
     NotEqual, // Inequality (!=).
     GreaterThan, // Greater than (>).
@@ -44,72 +143,1247 @@ pub enum BinaryOperator {
 
 // Define UnaryOperator enum for unary operations.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum UnaryOperator {
     Plus, // Unary plus (+).
     Minus, // Unary minus (-).
     Not, // Logical NOT.
-    Asc, // Ascending order for ORDER BY.
-    Desc, // Descending order for ORDER BY.
+}
+
+impl UnaryOperator {
+    // Binding power of the prefix operator: the parser parses the operand at this precedence.
+    // Signs sit between * / (60) and ^ (70), so -2 ^ 2 is -(2 ^ 2) while -a * b is (-a) * b.
+    // NOT sits between AND (20) and IS (25), as in standard SQL: NOT a = 1 is NOT (a = 1),
+    // while NOT a AND b is (NOT a) AND b.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            UnaryOperator::Plus | UnaryOperator::Minus => 65,
+            UnaryOperator::Not => 22,
+        }
+    }
+}
+
+// Define SortOrder enum for ORDER BY directions.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SortOrder {
+    Asc, // Ascending order.
+    Desc, // Descending order.
+}
+
+// Define OrderByItem struct for one ORDER BY key and its optional direction.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OrderByItem {
+    pub expr: Expression, // Sort key; a bare number refers to a select-list position.
+    pub order: Option<SortOrder>, // Explicit ASC/DESC, if given.
+}
+
+impl OrderByItem {
+    // Select-list position this item orders by (ORDER BY 1, 2), if the key is a bare number.
+    pub fn ordinal(&self) -> Option<u64> {
+        match self.expr {
+            Expression::Number(n) => Some(n),
+            _ => None,
+        }
+    }
+}
+
+// Define GroupByKind enum for one GROUP BY item.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GroupByKind {
+    Expression(Expression), // Plain grouping key (e.g., GROUP BY country).
+    Rollup(Vec<Expression>), // ROLLUP (a, b): groupings (a, b), (a), and ().
+    Cube(Vec<Expression>), // CUBE (a, b): every subset of the columns.
+    GroupingSets(Vec<Vec<Expression>>), // GROUPING SETS ((a), (b), ()): exactly the listed groupings.
+}
+
+impl GroupByKind {
+    // Every expression in the item, in source order.
+    pub fn expressions(&self) -> Vec<&Expression> {
+        match self {
+            GroupByKind::Expression(expr) => vec![expr],
+            GroupByKind::Rollup(exprs) | GroupByKind::Cube(exprs) => exprs.iter().collect(),
+            GroupByKind::GroupingSets(sets) => sets.iter().flatten().collect(),
+        }
+    }
+}
+
+// Define LikeSegment enum for one piece of a compiled LIKE pattern.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LikeSegment {
+    Literal(String), // Text that must match exactly.
+    AnyString, // % matches any run of characters, including none.
+    AnyChar, // _ matches exactly one character.
+}
+
+// Define LikePattern struct for a LIKE pattern split into segments (see Expression::compile_pattern),
+// e.g. for translating LIKE into a regular expression.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LikePattern {
+    pub segments: Vec<LikeSegment>, // Segments in pattern order; adjacent literal text is merged.
+}
+
+impl LikePattern {
+    // Split a LIKE pattern into segments. The escape character makes the character after it literal.
+    pub fn compile(pattern: &str, escape: Option<char>) -> LikePattern {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            let wildcard = match c {
+                _ if Some(c) == escape => {
+                    literal.push(chars.next().unwrap_or(c)); // A trailing escape stands for itself.
+                    continue;
+                }
+                '%' => LikeSegment::AnyString,
+                '_' => LikeSegment::AnyChar,
+                _ => {
+                    literal.push(c);
+                    continue;
+                }
+            };
+            if !literal.is_empty() {
+                segments.push(LikeSegment::Literal(std::mem::take(&mut literal)));
+            }
+            segments.push(wildcard);
+        }
+        if !literal.is_empty() {
+            segments.push(LikeSegment::Literal(literal));
+        }
+        LikePattern { segments }
+    }
+}
+
+// Define Limit enum for the LIMIT clause value.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Limit {
+    All, // LIMIT ALL: no limit, spelled out.
+    Count(u64), // LIMIT n (or FETCH NEXT n ROWS ONLY).
+}
+
+// Define OrderByResolution enum for what an ORDER BY item refers to (see Statement::resolve_order_by).
+#[derive(Debug, PartialEq, Clone)]
+pub enum OrderByResolution {
+    Alias { name: String, index: usize }, // A select-list alias; index is the 0-based select item.
+    Ordinal(u64), // A select-list position (ORDER BY 1).
+    Expression, // A standalone expression over the table's columns.
+}
+
+// Define ValidationError enum for semantic problems found after parsing.
+#[derive(Debug, PartialEq, Clone)]
+pub enum ValidationError {
+    OrdinalOutOfRange { ordinal: u64, column_count: usize }, // ORDER BY position outside the select list.
+}
+
+// Define BindError enum for failures of Statement::bind_params.
+#[derive(Debug, PartialEq, Clone)]
+pub enum BindError {
+    CountMismatch { expected: usize, supplied: usize }, // Number of values differs from the parameters.
+    InvalidPosition(u32), // $0: positional parameters start at $1.
 }
 
 // Define DBType enum for column data types.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DBType {
     Int, // Integer type.
     Bool, // Boolean type.
     Varchar(usize), // Variable-length string with length.
+    Date, // Calendar date.
+    Timestamp, // Date and time of day.
 }
 
 // Define Constraint enum for column constraints.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Constraint {
     PrimaryKey, // Primary key constraint.
     NotNull, // Not null constraint.
     Check(Expression), // Check constraint with expression.
+    Default(Expression), // Default value for the column.
+    AutoIncrement, // Auto-increment constraint (MySQL AUTO_INCREMENT, SQLite AUTOINCREMENT).
+    References { // Foreign key (e.g., REFERENCES users(id) ON DELETE CASCADE).
+        table: String, // Referenced table.
+        column: Option<String>, // Referenced column; None means the table's primary key.
+        referential_actions: ReferentialActions, // ON DELETE / ON UPDATE behavior.
+    },
+    Generated { // Computed column (e.g., GENERATED ALWAYS AS (qty * price) STORED).
+        expr: Expression, // Expression the value is computed from.
+        stored: bool, // STORED keeps the value on disk; VIRTUAL (the default) computes it on read.
+    },
 }
 
-// Define TableColumn struct for CREATE TABLE columns.
+// Define ReferentialActions struct for the ON DELETE / ON UPDATE clauses of a REFERENCES constraint.
+#[derive(Debug, PartialEq, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReferentialActions {
+    pub on_delete: Option<ReferentialAction>, // What happens when the referenced row is deleted.
+    pub on_update: Option<ReferentialAction>, // What happens when the referenced key changes.
+}
+
+// Define ReferentialAction enum for what a foreign key does when its target changes.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReferentialAction {
+    Cascade, // Delete or update the referencing rows too.
+    Restrict, // Reject the change.
+    SetNull, // Set the referencing column to NULL.
+    SetDefault, // Set the referencing column to its default.
+}
+
+// Define TableColumn struct for CREATE TABLE columns.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableColumn {
     pub column_name: String, // Column name.
     pub column_type: DBType, // Column data type.
+    pub collation: Option<String>, // Optional COLLATE name.
     pub constraints: Vec<Constraint>, // List of constraints.
+    pub comment: Option<String>, // Optional COMMENT text (MySQL).
+    pub span: Span, // Source range of the column definition (ignored by equality).
+}
+
+// Compare TableColumns by their semantic fields only, so equal definitions match wherever they appear.
+impl PartialEq for TableColumn {
+    fn eq(&self, other: &Self) -> bool {
+        self.column_name == other.column_name
+            && self.column_type == other.column_type
+            && self.collation == other.collation
+            && self.constraints == other.constraints
+            && self.comment == other.comment
+    }
 }
 
 // Define Statement enum for SQL statements.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Statement {
     Select { // SELECT with specific columns.
         columns: Vec<Expression>, // Selected columns or expressions.
-        from: String, // Table name.
+        into: Option<String>, // SELECT ... INTO new_table: create a table from the result.
+        from: TableSource, // Table or table-valued function.
         r#where: Option<Expression>, // Optional WHERE condition.
-        orderby: Vec<Expression>, // Optional ORDER BY expressions.
+        group_by: Vec<GroupByKind>, // Optional GROUP BY items.
+        having: Option<Expression>, // Optional HAVING condition.
+        orderby: Vec<OrderByItem>, // Optional ORDER BY items.
+        limit: Option<Limit>, // Optional LIMIT row count or LIMIT ALL.
+        offset: Option<u64>, // Optional OFFSET row count.
     },
     SelectAll { // SELECT * (2 bonus points).
-        from: String, // Table name.
+        into: Option<String>, // SELECT * INTO new_table: create a table from the result.
+        from: TableSource, // Table or table-valued function.
         r#where: Option<Expression>, // Optional WHERE condition.
-        orderby: Vec<Expression>, // Optional ORDER BY expressions.
+        group_by: Vec<GroupByKind>, // Optional GROUP BY items.
+        having: Option<Expression>, // Optional HAVING condition.
+        orderby: Vec<OrderByItem>, // Optional ORDER BY items.
+        limit: Option<Limit>, // Optional LIMIT row count or LIMIT ALL.
+        offset: Option<u64>, // Optional OFFSET row count.
     },
     CreateTable { // CREATE TABLE statement.
         table_name: String, // Table name.
+        if_not_exists: bool, // IF NOT EXISTS was given.
         column_list: Vec<TableColumn>, // List of columns.
     },
+    CreateTableAs { // CREATE TABLE ... AS SELECT (CTAS).
+        name: String, // Table name.
+        query: Box<Statement>, // Query providing the columns and rows.
+    },
+    Update { // UPDATE statement.
+        table_name: String, // Table being modified.
+        assignments: Vec<Assignment>, // SET column = value pairs, in order.
+        r#where: Option<Expression>, // Optional WHERE condition.
+        returning: Vec<Expression>, // Optional RETURNING list.
+    },
+    Insert { // INSERT INTO statement.
+        table_name: String, // Table receiving the rows.
+        columns: Vec<String>, // Optional target column list; empty means all columns.
+        source: InsertSource, // Where the new rows come from.
+        on_conflict: Option<OnConflict>, // Optional ON CONFLICT / ON DUPLICATE KEY UPDATE clause.
+        returning: Vec<Expression>, // Optional RETURNING list.
+    },
+    Delete { // DELETE FROM statement.
+        table_name: String, // Table losing the rows.
+        r#where: Option<Expression>, // Optional WHERE condition.
+        returning: Vec<Expression>, // Optional RETURNING list.
+    },
+    Values(Vec<Vec<Expression>>), // Standalone VALUES list: one tuple per row.
+    Union { // Combined results of two queries (e.g., SELECT a FROM t UNION ALL SELECT a FROM u).
+        left: Box<Statement>,
+        right: Box<Statement>,
+        all: bool, // UNION ALL keeps duplicate rows.
+    },
+    WithQuery { // SELECT preceded by WITH common table expressions.
+        ctes: Vec<Cte>, // Named subqueries, in definition order.
+        body: Box<Statement>, // The main SELECT.
+    },
+    Explain { // EXPLAIN [ANALYZE] <statement>.
+        analyze: bool, // EXPLAIN ANALYZE also runs the statement.
+        inner: Box<Statement>, // Statement whose plan is shown.
+    },
+    Describe { // DESCRIBE <table>.
+        table: String, // Table whose structure is shown.
+    },
+}
+
+// Define InsertSource enum for the rows an INSERT adds.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum InsertSource {
+    Values(Vec<Vec<Expression>>), // VALUES (..), (..): one tuple per row.
+    DefaultValues, // DEFAULT VALUES: a single row of column defaults.
+    Query(Box<Statement>), // SELECT ...: the query's result rows.
+}
+
+// Define OnConflict struct for the upsert clause of an INSERT (e.g., ON CONFLICT (id) DO NOTHING).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OnConflict {
+    pub target: Vec<String>, // Conflict target columns; empty for no target or ON DUPLICATE KEY.
+    pub action: ConflictAction, // What to do with a row that conflicts.
+}
+
+// Define ConflictAction enum for how an upsert resolves a conflicting row.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ConflictAction {
+    DoNothing, // ON CONFLICT ... DO NOTHING: skip the row.
+    DoUpdate(Vec<Assignment>), // ON CONFLICT ... DO UPDATE SET ...: update the existing row.
+    DuplicateKeyUpdate(Vec<Assignment>), // MySQL ON DUPLICATE KEY UPDATE ...: update the existing row.
+}
+
+impl OnConflict {
+    // The SET items applied to a conflicting row (empty for DO NOTHING).
+    pub fn assignments(&self) -> &[Assignment] {
+        match &self.action {
+            ConflictAction::DoNothing => &[],
+            ConflictAction::DoUpdate(assignments) | ConflictAction::DuplicateKeyUpdate(assignments) => assignments,
+        }
+    }
+}
+
+// Define Assignment struct for one SET item in UPDATE (e.g., count = count + 1).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Assignment {
+    pub column: String, // Column being assigned.
+    pub value: Expression, // New value; any expression, so it may refer to columns.
+}
+
+// Define Cte struct for one WITH definition (e.g., active AS (SELECT * FROM users)).
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cte {
+    pub name: String, // Name the main query refers to.
+    pub query: Box<Statement>, // The defining SELECT.
+}
+
+// Define TableSource enum for what a SELECT reads its rows from.
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TableSource {
+    Table(String), // Table name.
+    Function { // Table-valued function (e.g., generate_series(1, 10) AS s).
+        name: String,
+        args: Vec<Expression>,
+        alias: Option<String>, // Optional name for the produced rows.
+    },
+}
+
+impl TableSource {
+    // Append the bind parameters in a table function's arguments to params.
+    fn collect_placeholders(&self, params: &mut Vec<ParamKind>) {
+        if let TableSource::Function { args, .. } = self {
+            args.iter().for_each(|arg| arg.collect_placeholders(params));
+        }
+    }
+}
+
+impl Expression {
+    // Compile the pattern of a LIKE expression into segments, honoring its ESCAPE character.
+    // None if this is not a LIKE or its pattern is not a string literal (e.g., a bind parameter).
+    pub fn compile_pattern(&self) -> Option<LikePattern> {
+        match self {
+            Expression::Like { pattern, escape, .. } => match pattern.as_ref() {
+                Expression::String(pattern) => Some(LikePattern::compile(pattern, *escape)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    // Binding power of the expression's outermost operator, mirroring Parser::get_precedence;
+    // binary operators read theirs from the parser's OPERATORS table. Literals and identifiers bind tightest.
+    pub fn precedence(&self) -> u8 {
+        match self {
+            Expression::BinaryOperation { operator, .. } => operator.precedence(),
+            Expression::UnaryOperation { operator, .. } => operator.precedence(),
+            // Written with a leading minus, so it binds like unary minus (e.g., (-1) ^ 2).
+            Expression::SignedNumber(n) if *n < 0 => UnaryOperator::Minus.precedence(),
+            Expression::IsNull { .. } | Expression::IsBool { .. } => 25,
+            Expression::Quantified { .. } => COMPARISON_PRECEDENCE,
+            Expression::InList { .. }
+            | Expression::InSubquery { .. }
+            | Expression::Like { .. }
+            | Expression::Between { .. } => 40,
+            Expression::Collate { .. } => 90,
+            Expression::Cast { .. } => 95,
+            _ => u8::MAX, // Atoms never need parentheses.
+        }
+    }
+
+    // Format the expression as SQL with the default FormatOptions, adding parentheses only where
+    // precedence requires them (see format.rs).
+    pub fn to_sql(&self) -> String {
+        self.format(&FormatOptions::default())
+    }
+
+    // Equality up to commutativity and associativity: a + b matches b + a, (a AND b) AND c matches
+    // a AND (c AND b), and a = 1 matches 1 = a. Useful as a cache key comparison; the derived
+    // PartialEq stays strictly structural.
+    pub fn semantically_eq(&self, other: &Expression) -> bool {
+        canonicalize(self.clone()) == canonicalize(other.clone())
+    }
+
+    // Append the bind parameters in this expression to params, in left-to-right order.
+    fn collect_placeholders(&self, params: &mut Vec<ParamKind>) {
+        match self {
+            Expression::Parameter(kind) => params.push(kind.clone()),
+            Expression::BinaryOperation { left_operand, right_operand, .. } => {
+                left_operand.collect_placeholders(params);
+                right_operand.collect_placeholders(params);
+            }
+            Expression::UnaryOperation { operand, .. } => operand.collect_placeholders(params),
+            Expression::Collate { expr, .. }
+            | Expression::Cast { expr, .. }
+            | Expression::Alias { expr, .. }
+            | Expression::IsNull { expr, .. }
+            | Expression::IsBool { expr, .. } => expr.collect_placeholders(params),
+            Expression::Quantified { expr, subquery, .. } | Expression::InSubquery { expr, subquery, .. } => {
+                expr.collect_placeholders(params);
+                params.extend(subquery.placeholders());
+            }
+            Expression::InList { expr, list, .. } => {
+                expr.collect_placeholders(params);
+                list.iter().for_each(|item| item.collect_placeholders(params));
+            }
+            Expression::Like { expr, pattern, .. } => {
+                expr.collect_placeholders(params);
+                pattern.collect_placeholders(params);
+            }
+            Expression::Between { expr, low, high, .. } => {
+                expr.collect_placeholders(params);
+                low.collect_placeholders(params);
+                high.collect_placeholders(params);
+            }
+            Expression::FunctionCall { args, filter, .. } => {
+                args.iter().for_each(|arg| arg.collect_placeholders(params));
+                filter.iter().for_each(|condition| condition.collect_placeholders(params));
+            }
+            Expression::List(args) => args.iter().for_each(|arg| arg.collect_placeholders(params)),
+            Expression::WindowFunction { func, partition_by, order_by } => {
+                func.collect_placeholders(params);
+                partition_by.iter().for_each(|expr| expr.collect_placeholders(params));
+                order_by.iter().for_each(|item| item.expr.collect_placeholders(params));
+            }
+            Expression::Number(_)
+            | Expression::SignedNumber(_)
+            | Expression::String(_)
+            | Expression::Bool(_)
+            | Expression::Identifier(_)
+            | Expression::CompoundIdentifier(_)
+            | Expression::Wildcard
+            | Expression::TypedLiteral { .. }
+            | Expression::CurrentDate
+            | Expression::CurrentTimestamp
+            | Expression::Default => {}
+        }
+    }
+}
+
+// Quote a string literal for SQL output: single quotes are doubled and backslashes escaped,
+// matching what the tokenizer accepts (e.g., it's \ -> 'it''s \\').
+pub(crate) fn quote_string(s: &str) -> String {
+    format!("'{}'", s.replace('\\', "\\\\").replace('\'', "''"))
+}
+
+// Rewrite an expression tree bottom-up: children are mapped first, then f is applied to the rebuilt node.
+// Useful for query rewriting such as renaming columns or folding constants, e.g.
+// map_expression(expr, &mut |e| match e { Expression::Identifier(id) if id == "a" => Expression::Identifier("b".to_string()), e => e })
+pub fn map_expression(expr: Expression, f: &mut impl FnMut(Expression) -> Expression) -> Expression {
+    let mapped = match expr {
+        Expression::BinaryOperation { left_operand, operator, right_operand } => Expression::BinaryOperation {
+            left_operand: Box::new(map_expression(*left_operand, f)),
+            operator,
+            right_operand: Box::new(map_expression(*right_operand, f)),
+        },
+        Expression::UnaryOperation { operator, operand } => Expression::UnaryOperation {
+            operator,
+            operand: Box::new(map_expression(*operand, f)),
+        },
+        Expression::FunctionCall { name, distinct, args, filter } => Expression::FunctionCall {
+            name,
+            distinct,
+            args: args.into_iter().map(|arg| map_expression(arg, f)).collect(),
+            filter: filter.map(|condition| Box::new(map_expression(*condition, f))),
+        },
+        Expression::Alias { expr, alias } => Expression::Alias {
+            expr: Box::new(map_expression(*expr, f)),
+            alias,
+        },
+        Expression::List(items) => Expression::List(items.into_iter().map(|item| map_expression(item, f)).collect()),
+        Expression::WindowFunction { func, partition_by, order_by } => Expression::WindowFunction {
+            func: Box::new(map_expression(*func, f)),
+            partition_by: partition_by.into_iter().map(|expr| map_expression(expr, f)).collect(),
+            order_by: order_by
+                .into_iter()
+                .map(|item| OrderByItem { expr: map_expression(item.expr, f), order: item.order })
+                .collect(),
+        },
+        // The subquery is a separate statement and is left as is.
+        Expression::Quantified { expr, op, quantifier, subquery } => Expression::Quantified {
+            expr: Box::new(map_expression(*expr, f)),
+            op,
+            quantifier,
+            subquery,
+        },
+        Expression::Collate { expr, collation } => Expression::Collate {
+            expr: Box::new(map_expression(*expr, f)),
+            collation,
+        },
+        Expression::Cast { expr, data_type } => Expression::Cast {
+            expr: Box::new(map_expression(*expr, f)),
+            data_type,
+        },
+        Expression::InList { expr, list, negated } => Expression::InList {
+            expr: Box::new(map_expression(*expr, f)),
+            list: list.into_iter().map(|item| map_expression(item, f)).collect(),
+            negated,
+        },
+        Expression::InSubquery { expr, subquery, negated } => Expression::InSubquery {
+            expr: Box::new(map_expression(*expr, f)),
+            subquery,
+            negated,
+        },
+        Expression::Like { expr, pattern, negated, escape } => Expression::Like {
+            expr: Box::new(map_expression(*expr, f)),
+            pattern: Box::new(map_expression(*pattern, f)),
+            negated,
+            escape,
+        },
+        Expression::Between { expr, low, high, negated } => Expression::Between {
+            expr: Box::new(map_expression(*expr, f)),
+            low: Box::new(map_expression(*low, f)),
+            high: Box::new(map_expression(*high, f)),
+            negated,
+        },
+        Expression::IsNull { expr, negated } => Expression::IsNull {
+            expr: Box::new(map_expression(*expr, f)),
+            negated,
+        },
+        Expression::IsBool { expr, value, negated } => Expression::IsBool {
+            expr: Box::new(map_expression(*expr, f)),
+            value,
+            negated,
+        },
+        leaf => leaf, // Literals, identifiers, and parameters have no children.
+    };
+    f(mapped)
+}
+
+// Like map_expression, but also rewrites the statements inside IN/ANY/ALL subqueries.
+fn map_expression_deep(expr: Expression, f: &mut dyn FnMut(Expression) -> Expression) -> Expression {
+    map_expression(expr, &mut |e| {
+        let e = match e {
+            Expression::Quantified { expr, op, quantifier, subquery } => Expression::Quantified {
+                expr,
+                op,
+                quantifier,
+                subquery: Box::new(subquery.map_expressions(f)),
+            },
+            Expression::InSubquery { expr, subquery, negated } => Expression::InSubquery {
+                expr,
+                subquery: Box::new(subquery.map_expressions(f)),
+                negated,
+            },
+            e => e,
+        };
+        f(e)
+    })
+}
+
+// Apply map_expression_deep to each expression of a list.
+fn map_all(exprs: Vec<Expression>, f: &mut dyn FnMut(Expression) -> Expression) -> Vec<Expression> {
+    exprs.into_iter().map(|expr| map_expression_deep(expr, f)).collect()
+}
+
+// Normalize an expression with a few safe local rewrites, applied bottom-up via map_expression:
+// NOT (NOT x) -> x, - -x -> x, and comparisons between two literals of the same kind -> TRUE/FALSE.
+// Anything else is returned unchanged.
+pub fn simplify(expr: Expression) -> Expression {
+    map_expression(expr, &mut |e| match e {
+        Expression::UnaryOperation { operator: outer @ (UnaryOperator::Not | UnaryOperator::Minus), operand } => match *operand {
+            Expression::UnaryOperation { operator: inner, operand: x } if inner == outer => *x,
+            operand => Expression::UnaryOperation { operator: outer, operand: Box::new(operand) },
+        },
+        Expression::BinaryOperation { left_operand, operator, right_operand } => {
+            match compare_literals(&left_operand, &operator, &right_operand) {
+                Some(result) => Expression::Bool(result),
+                None => Expression::BinaryOperation { left_operand, operator, right_operand },
+            }
+        }
+        e => e,
+    })
+}
+
+// Evaluate a comparison between two literals of the same kind, or None if it cannot be folded.
+fn compare_literals(left: &Expression, operator: &BinaryOperator, right: &Expression) -> Option<bool> {
+    let ordering = match (left, right) {
+        (Expression::Number(l), Expression::Number(r)) => l.cmp(r),
+        (Expression::String(l), Expression::String(r)) => l.cmp(r),
+        (Expression::Bool(l), Expression::Bool(r)) => l.cmp(r),
+        _ => return None,
+    };
+    match operator {
+        BinaryOperator::Equal => Some(ordering.is_eq()),
+        BinaryOperator::NotEqual => Some(ordering.is_ne()),
+        BinaryOperator::GreaterThan => Some(ordering.is_gt()),
+        BinaryOperator::GreaterThanOrEqual => Some(ordering.is_ge()),
+        BinaryOperator::LessThan => Some(ordering.is_lt()),
+        BinaryOperator::LessThanOrEqual => Some(ordering.is_le()),
+        _ => None,
+    }
+}
+
+// Rewrite an expression into a canonical form for semantically_eq: chains of the same associative,
+// commutative operator (+, *, AND, OR) are flattened, their operands sorted, and rebuilt left-deep;
+// the two sides of = and != are sorted. Operands are ordered by their Debug text, which is arbitrary
+// but stable, so equivalent trees end up identical.
+fn canonicalize(expr: Expression) -> Expression {
+    map_expression(expr, &mut |e| match e {
+        Expression::BinaryOperation { left_operand, operator, right_operand } => match operator {
+            BinaryOperator::Plus | BinaryOperator::Multiply | BinaryOperator::And | BinaryOperator::Or => {
+                let mut operands = Vec::new();
+                flatten_chain(*left_operand, &operator, &mut operands);
+                flatten_chain(*right_operand, &operator, &mut operands);
+                operands.sort_by_cached_key(|operand| format!("{:?}", operand));
+                let mut operands = operands.into_iter();
+                let first = operands.next().expect("a binary operation has two operands");
+                operands.fold(first, |left, right| Expression::BinaryOperation {
+                    left_operand: Box::new(left),
+                    operator: operator.clone(),
+                    right_operand: Box::new(right),
+                })
+            }
+            BinaryOperator::Equal | BinaryOperator::NotEqual
+                if format!("{:?}", right_operand) < format!("{:?}", left_operand) =>
+            {
+                Expression::BinaryOperation { left_operand: right_operand, operator, right_operand: left_operand }
+            }
+            _ => Expression::BinaryOperation { left_operand, operator, right_operand },
+        },
+        e => e,
+    })
+}
+
+// Collect the operands of a chain of `operator` (e.g., a + b + c gives a, b, c).
+fn flatten_chain(expr: Expression, operator: &BinaryOperator, operands: &mut Vec<Expression>) {
+    match expr {
+        Expression::BinaryOperation { left_operand, operator: inner, right_operand } if inner == *operator => {
+            flatten_chain(*left_operand, operator, operands);
+            flatten_chain(*right_operand, operator, operands);
+        }
+        other => operands.push(other),
+    }
+}
+
+// Callbacks for walk_statement; override only the ones you need.
+// Every nested statement (CTE bodies, UNION arms, INSERT ... SELECT, and subqueries inside
+// expressions) and every expression is visited once, parents before children.
+pub trait StatementVisitor {
+    fn visit_statement(&mut self, _statement: &Statement) {}
+    fn visit_expression(&mut self, _expr: &Expression) {}
+}
+
+// Walk a statement and everything nested in it, e.g. to count SELECTs or collect every parameter
+// across subqueries. Unlike Statement::placeholders, this reaches CREATE TABLE CHECK/DEFAULT
+// expressions and subqueries alike.
+pub fn walk_statement<V: StatementVisitor + ?Sized>(statement: &Statement, visitor: &mut V) {
+    visitor.visit_statement(statement);
+    let walk_all = |exprs: &[Expression], visitor: &mut V| exprs.iter().for_each(|e| walk_expression(e, visitor));
+    match statement {
+        Statement::Select { columns, from, r#where, group_by, having, orderby, .. } => {
+            walk_all(columns, visitor);
+            if let TableSource::Function { args, .. } = from {
+                walk_all(args, visitor);
+            }
+            walk_select_clauses(r#where, group_by, having, orderby, visitor);
+        }
+        Statement::SelectAll { from, r#where, group_by, having, orderby, .. } => {
+            if let TableSource::Function { args, .. } = from {
+                walk_all(args, visitor);
+            }
+            walk_select_clauses(r#where, group_by, having, orderby, visitor);
+        }
+        Statement::CreateTable { column_list, .. } => {
+            for constraint in column_list.iter().flat_map(|column| &column.constraints) {
+                if let Constraint::Check(expr) | Constraint::Default(expr) | Constraint::Generated { expr, .. } = constraint {
+                    walk_expression(expr, visitor);
+                }
+            }
+        }
+        Statement::CreateTableAs { query, .. } => walk_statement(query, visitor),
+        Statement::Update { assignments, r#where, returning, .. } => {
+            assignments.iter().for_each(|assignment| walk_expression(&assignment.value, visitor));
+            if let Some(condition) = r#where {
+                walk_expression(condition, visitor);
+            }
+            walk_all(returning, visitor);
+        }
+        Statement::Insert { source, on_conflict, returning, .. } => {
+            match source {
+                InsertSource::Values(rows) => rows.iter().for_each(|row| walk_all(row, visitor)),
+                InsertSource::DefaultValues => {}
+                InsertSource::Query(query) => walk_statement(query, visitor),
+            }
+            if let Some(on_conflict) = on_conflict {
+                on_conflict.assignments().iter().for_each(|a| walk_expression(&a.value, visitor));
+            }
+            walk_all(returning, visitor);
+        }
+        Statement::Delete { r#where, returning, .. } => {
+            if let Some(condition) = r#where {
+                walk_expression(condition, visitor);
+            }
+            walk_all(returning, visitor);
+        }
+        Statement::Values(rows) => rows.iter().for_each(|row| walk_all(row, visitor)),
+        Statement::Union { left, right, .. } => {
+            walk_statement(left, visitor);
+            walk_statement(right, visitor);
+        }
+        Statement::WithQuery { ctes, body } => {
+            ctes.iter().for_each(|cte| walk_statement(&cte.query, visitor));
+            walk_statement(body, visitor);
+        }
+        Statement::Explain { inner, .. } => walk_statement(inner, visitor),
+        Statement::Describe { .. } => {}
+    }
+}
+
+// Walk the optional clauses shared by SELECT and SELECT *.
+fn walk_select_clauses<V: StatementVisitor + ?Sized>(
+    r#where: &Option<Expression>,
+    group_by: &[GroupByKind],
+    having: &Option<Expression>,
+    orderby: &[OrderByItem],
+    visitor: &mut V,
+) {
+    let group_by = group_by.iter().flat_map(|item| item.expressions());
+    r#where.iter().chain(group_by).chain(having).for_each(|expr| walk_expression(expr, visitor));
+    orderby.iter().for_each(|item| walk_expression(&item.expr, visitor));
+}
+
+// Walk an expression and its children, descending into subqueries with walk_statement.
+pub fn walk_expression<V: StatementVisitor + ?Sized>(expr: &Expression, visitor: &mut V) {
+    visitor.visit_expression(expr);
+    match expr {
+        Expression::BinaryOperation { left_operand, right_operand, .. } => {
+            walk_expression(left_operand, visitor);
+            walk_expression(right_operand, visitor);
+        }
+        Expression::UnaryOperation { operand, .. } => walk_expression(operand, visitor),
+        Expression::Collate { expr, .. }
+        | Expression::Cast { expr, .. }
+        | Expression::Alias { expr, .. }
+        | Expression::IsNull { expr, .. }
+        | Expression::IsBool { expr, .. } => walk_expression(expr, visitor),
+        Expression::Quantified { expr, subquery, .. } | Expression::InSubquery { expr, subquery, .. } => {
+            walk_expression(expr, visitor);
+            walk_statement(subquery, visitor);
+        }
+        Expression::InList { expr, list, .. } => {
+            walk_expression(expr, visitor);
+            list.iter().for_each(|item| walk_expression(item, visitor));
+        }
+        Expression::Like { expr, pattern, .. } => {
+            walk_expression(expr, visitor);
+            walk_expression(pattern, visitor);
+        }
+        Expression::Between { expr, low, high, .. } => {
+            walk_expression(expr, visitor);
+            walk_expression(low, visitor);
+            walk_expression(high, visitor);
+        }
+        Expression::FunctionCall { args, filter, .. } => {
+            args.iter().for_each(|arg| walk_expression(arg, visitor));
+            if let Some(condition) = filter {
+                walk_expression(condition, visitor);
+            }
+        }
+        Expression::List(args) => args.iter().for_each(|arg| walk_expression(arg, visitor)),
+        Expression::WindowFunction { func, partition_by, order_by } => {
+            walk_expression(func, visitor);
+            partition_by.iter().for_each(|expr| walk_expression(expr, visitor));
+            order_by.iter().for_each(|item| walk_expression(&item.expr, visitor));
+        }
+        Expression::Number(_)
+        | Expression::SignedNumber(_)
+        | Expression::String(_)
+        | Expression::Bool(_)
+        | Expression::Identifier(_)
+        | Expression::CompoundIdentifier(_)
+        | Expression::Parameter(_)
+        | Expression::Wildcard
+        | Expression::TypedLiteral { .. }
+        | Expression::CurrentDate
+        | Expression::CurrentTimestamp
+        | Expression::Default => {}
+    }
+}
+
+impl Statement {
+    // Collect the statement's bind parameters in the order they appear in the query text,
+    // so driver code can map user-supplied values to positions.
+    pub fn placeholders(&self) -> Vec<ParamKind> {
+        let mut params = Vec::new();
+        match self {
+            Statement::Select { columns, from, r#where, group_by, having, orderby, .. } => {
+                columns.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+                from.collect_placeholders(&mut params);
+                r#where.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+                group_by.iter().flat_map(|item| item.expressions()).for_each(|expr| expr.collect_placeholders(&mut params));
+                having.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+                orderby.iter().for_each(|item| item.expr.collect_placeholders(&mut params));
+            }
+            Statement::SelectAll { from, r#where, group_by, having, orderby, .. } => {
+                from.collect_placeholders(&mut params);
+                r#where.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+                group_by.iter().flat_map(|item| item.expressions()).for_each(|expr| expr.collect_placeholders(&mut params));
+                having.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+                orderby.iter().for_each(|item| item.expr.collect_placeholders(&mut params));
+            }
+            Statement::CreateTable { column_list, .. } => {
+                for constraint in column_list.iter().flat_map(|column| &column.constraints) {
+                    if let Constraint::Check(expr) | Constraint::Default(expr) | Constraint::Generated { expr, .. } = constraint {
+                        expr.collect_placeholders(&mut params);
+                    }
+                }
+            }
+            Statement::Update { assignments, r#where, returning, .. } => {
+                assignments.iter().for_each(|assignment| assignment.value.collect_placeholders(&mut params));
+                r#where.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+                returning.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+            }
+            Statement::Insert { source, on_conflict, returning, .. } => {
+                match source {
+                    InsertSource::Values(rows) => {
+                        rows.iter().flatten().for_each(|expr| expr.collect_placeholders(&mut params))
+                    }
+                    InsertSource::DefaultValues => {}
+                    InsertSource::Query(query) => params.extend(query.placeholders()),
+                }
+                if let Some(on_conflict) = on_conflict {
+                    on_conflict.assignments().iter().for_each(|a| a.value.collect_placeholders(&mut params));
+                }
+                returning.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+            }
+            Statement::Delete { r#where, returning, .. } => {
+                r#where.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+                returning.iter().for_each(|expr| expr.collect_placeholders(&mut params));
+            }
+            Statement::Values(rows) => rows.iter().flatten().for_each(|expr| expr.collect_placeholders(&mut params)),
+            Statement::CreateTableAs { query, .. } => params.extend(query.placeholders()),
+            Statement::Union { left, right, .. } => {
+                params.extend(left.placeholders());
+                params.extend(right.placeholders());
+            }
+            Statement::WithQuery { ctes, body } => {
+                ctes.iter().for_each(|cte| params.extend(cte.query.placeholders()));
+                params.extend(body.placeholders());
+            }
+            Statement::Explain { inner, .. } => params.extend(inner.placeholders()),
+            Statement::Describe { .. } => {}
+        }
+        params
+    }
+
+    // Replace the ? and $n bind parameters with the given values: the k-th ? (in the order of
+    // placeholders()) takes values[k - 1] and $n takes values[n - 1]. Named parameters are left in place.
+    // Exactly as many values as the number of ?s (or the highest $n, if larger) must be supplied.
+    pub fn bind_params(&self, values: &[Expression]) -> Result<Statement, BindError> {
+        let mut anonymous = 0;
+        let mut highest = 0;
+        for param in self.placeholders() {
+            match param {
+                ParamKind::Anonymous => anonymous += 1,
+                ParamKind::Positional(0) => return Err(BindError::InvalidPosition(0)),
+                ParamKind::Positional(n) => highest = highest.max(n as usize),
+                ParamKind::Named(_) => {}
+            }
+        }
+        let expected = usize::max(anonymous, highest);
+        if values.len() != expected {
+            return Err(BindError::CountMismatch { expected, supplied: values.len() });
+        }
+        let mut next = 0;
+        Ok(self.clone().map_expressions(&mut |e| match e {
+            Expression::Parameter(ParamKind::Anonymous) => {
+                next += 1;
+                values[next - 1].clone()
+            }
+            Expression::Parameter(ParamKind::Positional(n)) => values[n as usize - 1].clone(),
+            e => e,
+        }))
+    }
+
+    // Rewrite every expression in the statement bottom-up, visiting them in the same order as
+    // placeholders(): subqueries, CTEs, and CREATE TABLE CHECK/DEFAULT expressions included.
+    fn map_expressions(self, f: &mut dyn FnMut(Expression) -> Expression) -> Statement {
+        let map_option = |expr: Option<Expression>, f: &mut dyn FnMut(Expression) -> Expression| {
+            expr.map(|expr| map_expression_deep(expr, f))
+        };
+        let map_group_by = |group_by: Vec<GroupByKind>, f: &mut dyn FnMut(Expression) -> Expression| {
+            group_by
+                .into_iter()
+                .map(|item| match item {
+                    GroupByKind::Expression(expr) => GroupByKind::Expression(map_expression_deep(expr, f)),
+                    GroupByKind::Rollup(exprs) => GroupByKind::Rollup(map_all(exprs, f)),
+                    GroupByKind::Cube(exprs) => GroupByKind::Cube(map_all(exprs, f)),
+                    GroupByKind::GroupingSets(sets) => {
+                        GroupByKind::GroupingSets(sets.into_iter().map(|set| map_all(set, f)).collect())
+                    }
+                })
+                .collect()
+        };
+        let map_from = |from: TableSource, f: &mut dyn FnMut(Expression) -> Expression| match from {
+            TableSource::Function { name, args, alias } => TableSource::Function { name, args: map_all(args, f), alias },
+            table => table,
+        };
+        let map_order_by = |orderby: Vec<OrderByItem>, f: &mut dyn FnMut(Expression) -> Expression| {
+            orderby
+                .into_iter()
+                .map(|item| OrderByItem { expr: map_expression_deep(item.expr, f), order: item.order })
+                .collect()
+        };
+        match self {
+            Statement::Select { columns, into, from, r#where, group_by, having, orderby, limit, offset } => Statement::Select {
+                columns: map_all(columns, f),
+                into,
+                from: map_from(from, f),
+                r#where: map_option(r#where, f),
+                group_by: map_group_by(group_by, f),
+                having: map_option(having, f),
+                orderby: map_order_by(orderby, f),
+                limit,
+                offset,
+            },
+            Statement::SelectAll { into, from, r#where, group_by, having, orderby, limit, offset } => Statement::SelectAll {
+                into,
+                from: map_from(from, f),
+                r#where: map_option(r#where, f),
+                group_by: map_group_by(group_by, f),
+                having: map_option(having, f),
+                orderby: map_order_by(orderby, f),
+                limit,
+                offset,
+            },
+            Statement::CreateTable { table_name, if_not_exists, column_list } => Statement::CreateTable {
+                table_name,
+                if_not_exists,
+                column_list: column_list
+                    .into_iter()
+                    .map(|column| TableColumn {
+                        constraints: column
+                            .constraints
+                            .into_iter()
+                            .map(|constraint| match constraint {
+                                Constraint::Check(expr) => Constraint::Check(map_expression_deep(expr, f)),
+                                Constraint::Default(expr) => Constraint::Default(map_expression_deep(expr, f)),
+                                Constraint::Generated { expr, stored } => {
+                                    Constraint::Generated { expr: map_expression_deep(expr, f), stored }
+                                }
+                                other => other,
+                            })
+                            .collect(),
+                        ..column
+                    })
+                    .collect(),
+            },
+            Statement::CreateTableAs { name, query } => {
+                Statement::CreateTableAs { name, query: Box::new(query.map_expressions(f)) }
+            }
+            Statement::Update { table_name, assignments, r#where, returning } => Statement::Update {
+                table_name,
+                assignments: assignments
+                    .into_iter()
+                    .map(|assignment| Assignment {
+                        column: assignment.column,
+                        value: map_expression_deep(assignment.value, f),
+                    })
+                    .collect(),
+                r#where: map_option(r#where, f),
+                returning: map_all(returning, f),
+            },
+            Statement::Insert { table_name, columns, source, on_conflict, returning } => Statement::Insert {
+                table_name,
+                columns,
+                source: match source {
+                    InsertSource::Values(rows) => InsertSource::Values(rows.into_iter().map(|row| map_all(row, f)).collect()),
+                    InsertSource::DefaultValues => InsertSource::DefaultValues,
+                    InsertSource::Query(query) => InsertSource::Query(Box::new(query.map_expressions(f))),
+                },
+                on_conflict: on_conflict.map(|OnConflict { target, action }| {
+                    let mut map_assignments = |assignments: Vec<Assignment>| -> Vec<Assignment> {
+                        assignments
+                            .into_iter()
+                            .map(|assignment| Assignment {
+                                column: assignment.column,
+                                value: map_expression_deep(assignment.value, f),
+                            })
+                            .collect()
+                    };
+                    let action = match action {
+                        ConflictAction::DoNothing => ConflictAction::DoNothing,
+                        ConflictAction::DoUpdate(assignments) => ConflictAction::DoUpdate(map_assignments(assignments)),
+                        ConflictAction::DuplicateKeyUpdate(assignments) => {
+                            ConflictAction::DuplicateKeyUpdate(map_assignments(assignments))
+                        }
+                    };
+                    OnConflict { target, action }
+                }),
+                returning: map_all(returning, f),
+            },
+            Statement::Delete { table_name, r#where, returning } => Statement::Delete {
+                table_name,
+                r#where: map_option(r#where, f),
+                returning: map_all(returning, f),
+            },
+            Statement::Values(rows) => Statement::Values(rows.into_iter().map(|row| map_all(row, f)).collect()),
+            Statement::Union { left, right, all } => Statement::Union {
+                left: Box::new(left.map_expressions(f)),
+                right: Box::new(right.map_expressions(f)),
+                all,
+            },
+            Statement::WithQuery { ctes, body } => Statement::WithQuery {
+                ctes: ctes
+                    .into_iter()
+                    .map(|cte| Cte { name: cte.name, query: Box::new(cte.query.map_expressions(f)) })
+                    .collect(),
+                body: Box::new(body.map_expressions(f)),
+            },
+            Statement::Explain { analyze, inner } => Statement::Explain { analyze, inner: Box::new(inner.map_expressions(f)) },
+            Statement::Describe { table } => Statement::Describe { table },
+        }
+    }
+
+    // Classify each ORDER BY item as a select alias, an ordinal, or a standalone expression.
+    // Only a bare identifier can name an alias; statements without ORDER BY give an empty list.
+    pub fn resolve_order_by(&self) -> Vec<OrderByResolution> {
+        let (columns, orderby): (&[Expression], &[OrderByItem]) = match self {
+            Statement::Select { columns, orderby, .. } => (columns, orderby),
+            Statement::SelectAll { orderby, .. } => (&[], orderby),
+            Statement::WithQuery { body, .. } => return body.resolve_order_by(),
+            // ORDER BY inside a UNION belongs to its last SELECT.
+            Statement::Union { right, .. } => return right.resolve_order_by(),
+            Statement::Explain { inner, .. } => return inner.resolve_order_by(),
+            Statement::CreateTable { .. }
+            | Statement::Update { .. }
+            | Statement::Insert { .. }
+            | Statement::Delete { .. }
+            | Statement::Values(_)
+            | Statement::CreateTableAs { .. }
+            | Statement::Describe { .. } => return Vec::new(),
+        };
+        orderby
+            .iter()
+            .map(|item| {
+                if let Some(ordinal) = item.ordinal() {
+                    return OrderByResolution::Ordinal(ordinal);
+                }
+                let Expression::Identifier(name) = &item.expr else {
+                    return OrderByResolution::Expression;
+                };
+                let index = columns
+                    .iter()
+                    .position(|column| matches!(column, Expression::Alias { alias, .. } if alias == name));
+                match index {
+                    Some(index) => OrderByResolution::Alias { name: name.clone(), index },
+                    None => OrderByResolution::Expression,
+                }
+            })
+            .collect()
+    }
+
+    // Number of columns the query produces: one per select-list item (aggregates and expressions
+    // included). None for SELECT *, which needs the schema, and for statements that return no rows.
+    pub fn output_column_count(&self) -> Option<usize> {
+        match self {
+            Statement::Select { columns, .. } => Some(columns.len()),
+            Statement::Values(rows) => rows.first().map(|row| row.len()),
+            // Both sides of a UNION have the same arity, so the left one decides.
+            Statement::Union { left, .. } => left.output_column_count(),
+            Statement::WithQuery { body, .. } => body.output_column_count(),
+            Statement::SelectAll { .. }
+            | Statement::CreateTable { .. }
+            | Statement::CreateTableAs { .. }
+            | Statement::Update { .. }
+            | Statement::Insert { .. }
+            | Statement::Delete { .. }
+            | Statement::Explain { .. }
+            | Statement::Describe { .. } => None,
+        }
+    }
+
+    // Whether the statement only reads data: SELECTs and VALUES, and CTEs/unions made only of them.
+    // Tools can use this to reject anything that creates or modifies data.
+    pub fn is_read_only(&self) -> bool {
+        match self {
+            // SELECT ... INTO creates a table.
+            Statement::Select { into, .. } | Statement::SelectAll { into, .. } => into.is_none(),
+            Statement::Values(_) => true,
+            Statement::Union { left, right, .. } => left.is_read_only() && right.is_read_only(),
+            Statement::WithQuery { ctes, body } => ctes.iter().all(|cte| cte.query.is_read_only()) && body.is_read_only(),
+            // Plain EXPLAIN only plans the statement; EXPLAIN ANALYZE actually runs it.
+            Statement::Explain { analyze, inner } => !analyze || inner.is_read_only(),
+            Statement::Describe { .. } => true,
+            Statement::CreateTable { .. }
+            | Statement::CreateTableAs { .. }
+            | Statement::Update { .. }
+            | Statement::Insert { .. }
+            | Statement::Delete { .. } => false,
+        }
+    }
+
+    // Check semantic rules the grammar cannot express, e.g. that ORDER BY ordinals
+    // refer to an existing select-list position (1..=output_column_count()). SELECT * has no known
+    // column count, so only ordinal 0 is rejected there.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let column_count = self.output_column_count();
+        let orderby = match self {
+            Statement::Select { orderby, .. } | Statement::SelectAll { orderby, .. } => orderby,
+            Statement::CreateTable { .. }
+            | Statement::Update { .. }
+            | Statement::Delete { .. }
+            | Statement::Values(_)
+            | Statement::Describe { .. } => return Ok(()),
+            Statement::Insert { source, .. } => {
+                return match source {
+                    InsertSource::Query(query) => query.validate(),
+                    _ => Ok(()),
+                };
+            }
+            Statement::WithQuery { ctes, body } => {
+                for cte in ctes {
+                    cte.query.validate()?;
+                }
+                return body.validate();
+            }
+            Statement::Union { left, right, .. } => {
+                left.validate()?;
+                return right.validate();
+            }
+            Statement::CreateTableAs { query, .. } => return query.validate(),
+            Statement::Explain { inner, .. } => return inner.validate(),
+        };
+        for ordinal in orderby.iter().filter_map(|item| item.ordinal()) {
+            let in_range = ordinal >= 1 && column_count.is_none_or(|count| ordinal <= count as u64);
+            if !in_range {
+                return Err(ValidationError::OrdinalOutOfRange {
+                    ordinal,
+                    column_count: column_count.unwrap_or(0),
+                });
+            }
+        }
+        Ok(())
+    }
 }
 
 // Implement Display for Expression to format as SQL-like string.
 impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().expr(self))
+    }
+}
+
+// Keyword tested by IS [NOT] TRUE / FALSE / UNKNOWN.
+pub(crate) fn truth_value(value: Option<bool>) -> &'static str {
+    match value {
+        Some(true) => "TRUE",
+        Some(false) => "FALSE",
+        None => "UNKNOWN",
+    }
+}
+
+// Implement Display for OrderByItem to show the key and its direction.
+impl fmt::Display for OrderByItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().order_by_item(self))
+    }
+}
+
+// Implement Display for ValidationError to show user-friendly messages.
+impl fmt::Display for ValidationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Expression::Number(n) => write!(f, "{}", n),
-            Expression::String(s) => write!(f, "\"{}\"", s),
-            Expression::Bool(b) => write!(f, "{}", b),
-            Expression::Identifier(id) => write!(f, "{}", id),
-            Expression::BinaryOperation { left_operand, operator, right_operand } => {
-                write!(f, "({} {} {})", left_operand, operator, right_operand) // Parentheses for clarity.
+            ValidationError::OrdinalOutOfRange { ordinal, column_count } => {
+                write!(f, "ORDER BY position {} is not in the select list (1..={})", ordinal, column_count)
             }
-            Expression::UnaryOperation { operator, operand } => {
-                write!(f, "{} {}", operator, operand)
+        }
+    }
+}
+
+// Implement Display for BindError to show user-friendly messages.
+impl fmt::Display for BindError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BindError::CountMismatch { expected, supplied } => {
+                write!(f, "Expected {} bind values, got {}", expected, supplied)
             }
+            BindError::InvalidPosition(n) => write!(f, "Invalid parameter position ${}", n),
+        }
+    }
+}
+
+// Implement Display for GroupByKind to show the GROUP BY item.
+impl fmt::Display for GroupByKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().group_by_item(self))
+    }
+}
+
+// Implement Display for Limit to show the LIMIT value.
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Limit::All => write!(f, "ALL"),
+            Limit::Count(n) => write!(f, "{}", n),
+        }
+    }
+}
+
+// Implement Display for Quantifier to show the SQL keyword.
+impl fmt::Display for Quantifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Quantifier::All => write!(f, "ALL"),
+            Quantifier::Any => write!(f, "ANY"),
+            Quantifier::Some => write!(f, "SOME"),
+        }
+    }
+}
+
+// Implement Display for ParamKind to reproduce the placeholder syntax.
+impl fmt::Display for ParamKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParamKind::Anonymous => write!(f, "?"),
+            ParamKind::Positional(n) => write!(f, "${}", n),
+            ParamKind::Named(name) => write!(f, "{}", name),
         }
     }
 }
@@ -122,6 +1396,7 @@ impl fmt::Display for BinaryOperator {
             BinaryOperator::Minus => write!(f, "-"),
             BinaryOperator::Multiply => write!(f, "*"),
             BinaryOperator::Divide => write!(f, "/"),
+            BinaryOperator::Power => write!(f, "^"),
             BinaryOperator::Equal => write!(f, "="), // Matches Token::Equal.
             BinaryOperator::NotEqual => write!(f, "!="),
             BinaryOperator::GreaterThan => write!(f, ">"),
@@ -141,8 +1416,6 @@ impl fmt::Display for UnaryOperator {
             UnaryOperator::Plus => write!(f, "+"),
             UnaryOperator::Minus => write!(f, "-"),
             UnaryOperator::Not => write!(f, "NOT"),
-            UnaryOperator::Asc => write!(f, "ASC"),
-            UnaryOperator::Desc => write!(f, "DESC"),
         }
     }
 }
@@ -154,17 +1427,27 @@ impl fmt::Display for DBType {
             DBType::Int => write!(f, "INT"),
             DBType::Bool => write!(f, "BOOL"),
             DBType::Varchar(len) => write!(f, "VARCHAR({})", len),
+            DBType::Date => write!(f, "DATE"),
+            DBType::Timestamp => write!(f, "TIMESTAMP"),
         }
     }
 }
 
 // Implement Display for Constraint to show SQL constraint syntax.
 impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().constraint(self))
+    }
+}
+
+// Implement Display for ReferentialAction to show the SQL action.
+impl fmt::Display for ReferentialAction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Constraint::PrimaryKey => write!(f, "PRIMARY KEY"),
-            Constraint::NotNull => write!(f, "NOT NULL"),
-            Constraint::Check(expr) => write!(f, "CHECK ({})", expr),
+            ReferentialAction::Cascade => write!(f, "CASCADE"),
+            ReferentialAction::Restrict => write!(f, "RESTRICT"),
+            ReferentialAction::SetNull => write!(f, "SET NULL"),
+            ReferentialAction::SetDefault => write!(f, "SET DEFAULT"),
         }
     }
 }
@@ -172,68 +1455,48 @@ impl fmt::Display for Constraint {
 // Implement Display for TableColumn to show column definition.
 impl fmt::Display for TableColumn {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {}", self.column_name, self.column_type)?;
-        for constraint in &self.constraints {
-            write!(f, " {}", constraint)?;
-        }
-        Ok(())
+        f.write_str(&SqlFormatter::display().column(self))
     }
 }
 
 // Implement Display for Statement to format as SQL query.
 impl fmt::Display for Statement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Statement::Select { columns, from, r#where, orderby } => {
-                write!(f, "SELECT ")?;
-                for (i, col) in columns.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "{}", col)?;
-                }
-                write!(f, " FROM {}", from)?;
-                if let Some(w) = r#where {
-                    write!(f, " WHERE {}", w)?;
-                }
-                if !orderby.is_empty() {
-                    write!(f, " ORDER BY ")?;
-                    for (i, ord) in orderby.iter().enumerate() {
-                        if i > 0 {
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "{}", ord)?;
-                    }
-                }
-                Ok(())
-            }
-            Statement::SelectAll { from, r#where, orderby } => {
-                // Format SELECT * queries (2 bonus points).
-                write!(f, "SELECT * FROM {}", from)?;
-                if let Some(w) = r#where {
-                    write!(f, " WHERE {}", w)?;
-                }
-                if !orderby.is_empty() {
-                    write!(f, " ORDER BY ")?;
-                    for (i, ord) in orderby.iter().enumerate() {
-                        if i > 0 {
-                            write!(f, ", ")?;
-                        }
-                        write!(f, "{}", ord)?;
-                    }
-                }
-                Ok(())
-            }
-            Statement::CreateTable { table_name, column_list } => {
-                write!(f, "CREATE TABLE {} (", table_name)?;
-                for (i, col) in column_list.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "{}", col)?;
-                }
-                write!(f, ")")
-            }
-        }
+        f.write_str(&SqlFormatter::display().statement(self))
     }
-}
\ No newline at end of file
+}
+
+// Implement Display for TableSource to show the FROM target.
+impl fmt::Display for TableSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().table_source(self))
+    }
+}
+
+// Implement Display for InsertSource to show the VALUES, DEFAULT VALUES, or SELECT part.
+impl fmt::Display for InsertSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().insert_source(self))
+    }
+}
+
+// Implement Display for OnConflict to show the ON CONFLICT or ON DUPLICATE KEY UPDATE clause.
+impl fmt::Display for OnConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().on_conflict(self))
+    }
+}
+
+// Implement Display for Assignment to show the SET item.
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().assignment(self))
+    }
+}
+
+// Implement Display for Cte to show the WITH definition.
+impl fmt::Display for Cte {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&SqlFormatter::display().cte(self))
+    }
+}
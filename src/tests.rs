@@ -11,10 +11,11 @@
 #[cfg(test)]
 mod tests {
     // Import necessary types and modules for testing.
-    use crate::parser::Parser;
-    use crate::statement::{Statement, Expression, BinaryOperator, UnaryOperator, TableColumn, DBType, Constraint};
-    use crate::tokenizer::{Tokenizer, TokenizerError};
-    use crate::token::{Token, Keyword};
+    use crate::parser::{Associativity, Parser, ParseError, COMPARISON_PRECEDENCE, ParserOptions, parse_one, parse_one_with_options, parse_script, parse_script_with_options, parse_table_columns, parse_table_columns_with_options, try_parse};
+    use crate::statement::{Statement, Expression, BinaryOperator, UnaryOperator, TableColumn, DBType, Constraint, ReferentialAction, ReferentialActions, ParamKind, OrderByItem, SortOrder, ValidationError, BindError, Cte, Assignment, OrderByResolution, Quantifier, Limit, InsertSource, OnConflict, ConflictAction, GroupByKind, TableSource, LikePattern, LikeSegment, StatementVisitor, map_expression, simplify, walk_statement};
+    use crate::tokenizer::{split_statements, split_statements_with_options, Tokenizer, TokenizerError, TokenizerOptions};
+    use crate::token::{Token, Keyword, Span, Spanned};
+    use crate::format::{FormatOptions, QuoteStyle};
 
     // Test single-character tokens (e.g., (, ), +) for tokenizer.
     #[test]
@@ -131,24 +132,17 @@ mod tests {
     fn test_pratt_parser_precedence() {
         let input = "1 + 2 * 3;";
         let mut parser = Parser::new(input);
-        let statement = parser.parse().unwrap();
+        let expr = parser.parse_expression_only().unwrap();
         assert_eq!(
-            statement,
-            Statement::Select {
-                columns: vec![
-                    Expression::BinaryOperation {
-                        left_operand: Box::new(Expression::Number(1)),
-                        operator: BinaryOperator::Plus,
-                        right_operand: Box::new(Expression::BinaryOperation {
-                            left_operand: Box::new(Expression::Number(2)),
-                            operator: BinaryOperator::Multiply,
-                            right_operand: Box::new(Expression::Number(3)),
-                        }),
-                    }
-                ],
-                from: "".to_string(), // Empty table name (simplified test).
-                r#where: None,
-                orderby: vec![],
+            expr,
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Number(1)),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Number(2)),
+                    operator: BinaryOperator::Multiply,
+                    right_operand: Box::new(Expression::Number(3)),
+                }),
             }
         );
     }
@@ -158,24 +152,17 @@ mod tests {
     fn test_pratt_parser_parentheses() {
         let input = "(1 + 2) * 3;";
         let mut parser = Parser::new(input);
-        let statement = parser.parse().unwrap();
+        let expr = parser.parse_expression_only().unwrap();
         assert_eq!(
-            statement,
-            Statement::Select {
-                columns: vec![
-                    Expression::BinaryOperation {
-                        left_operand: Box::new(Expression::BinaryOperation {
-                            left_operand: Box::new(Expression::Number(1)),
-                            operator: BinaryOperator::Plus,
-                            right_operand: Box::new(Expression::Number(2)),
-                        }),
-                        operator: BinaryOperator::Multiply,
-                        right_operand: Box::new(Expression::Number(3)),
-                    }
-                ],
-                from: "".to_string(),
-                r#where: None,
-                orderby: vec![],
+            expr,
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Number(1)),
+                    operator: BinaryOperator::Plus,
+                    right_operand: Box::new(Expression::Number(2)),
+                }),
+                operator: BinaryOperator::Multiply,
+                right_operand: Box::new(Expression::Number(3)),
             }
         );
     }
@@ -185,23 +172,16 @@ mod tests {
     fn test_pratt_parser_unary_operation() {
         let input = "-5 + 6;";
         let mut parser = Parser::new(input);
-        let statement = parser.parse().unwrap();
+        let expr = parser.parse_expression_only().unwrap();
         assert_eq!(
-            statement,
-            Statement::Select {
-                columns: vec![
-                    Expression::BinaryOperation {
-                        left_operand: Box::new(Expression::UnaryOperation {
-                            operand: Box::new(Expression::Number(5)),
-                            operator: UnaryOperator::Minus,
-                        }),
-                        operator: BinaryOperator::Plus,
-                        right_operand: Box::new(Expression::Number(6)),
-                    }
-                ],
-                from: "".to_string(),
-                r#where: None,
-                orderby: vec![],
+            expr,
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::UnaryOperation {
+                    operand: Box::new(Expression::Number(5)),
+                    operator: UnaryOperator::Minus,
+                }),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::Number(6)),
             }
         );
     }
@@ -212,7 +192,7 @@ mod tests {
         let input = "1 + #;";
         let mut parser = Parser::new(input);
         let result = parser.parse();
-        assert!(matches!(result, Err(_))); // Error depends on parser state.
+        assert!(result.is_err()); // Error depends on parser state.
     }
 
     // Test simple SELECT statement parsing.
@@ -228,9 +208,14 @@ mod tests {
                     Expression::Identifier("id".to_string()),
                     Expression::Identifier("name".to_string()),
                 ],
-                from: "users".to_string(),
+                into: None,
+                from: TableSource::Table("users".to_string()),
                 r#where: None,
+                group_by: vec![],
+                having: None,
                 orderby: vec![],
+                limit: None,
+                offset: None,
             }
         );
     }
@@ -245,18 +230,23 @@ mod tests {
             statement,
             Statement::Select {
                 columns: vec![Expression::Identifier("name".to_string())],
-                from: "users".to_string(),
+                into: None,
+                from: TableSource::Table("users".to_string()),
                 r#where: Some(Expression::BinaryOperation {
                     left_operand: Box::new(Expression::Identifier("age".to_string())),
                     operator: BinaryOperator::GreaterThan,
                     right_operand: Box::new(Expression::Number(18)),
                 }),
+                group_by: vec![],
+                having: None,
                 orderby: vec![
-                    Expression::UnaryOperation {
-                        operand: Box::new(Expression::Identifier("name".to_string())),
-                        operator: UnaryOperator::Asc,
+                    OrderByItem {
+                        expr: Expression::Identifier("name".to_string()),
+                        order: Some(SortOrder::Asc),
                     }
                 ],
+                limit: None,
+                offset: None,
             }
         );
     }
@@ -271,20 +261,30 @@ mod tests {
             statement,
             Statement::CreateTable {
                 table_name: "users".to_string(),
+                if_not_exists: false,
                 column_list: vec![
                     TableColumn {
                         column_name: "id".to_string(),
                         column_type: DBType::Int,
+                        collation: None,
+                        comment: None,
+                        span: Span::default(),
                         constraints: vec![Constraint::PrimaryKey],
                     },
                     TableColumn {
                         column_name: "name".to_string(),
                         column_type: DBType::Varchar(50),
+                        collation: None,
+                        comment: None,
+                        span: Span::default(),
                         constraints: vec![Constraint::NotNull],
                     },
                     TableColumn {
                         column_name: "age".to_string(),
                         column_type: DBType::Int,
+                        collation: None,
+                        comment: None,
+                        span: Span::default(),
                         constraints: vec![Constraint::Check(
                             Expression::BinaryOperation {
                                 left_operand: Box::new(Expression::Identifier("age".to_string())),
@@ -304,7 +304,7 @@ mod tests {
         let input = "SELECT name;";
         let mut parser = Parser::new(input);
         let result = parser.parse();
-        assert!(matches!(result, Err(_)));
+        assert!(result.is_err());
     }
 
     // Test error handling for invalid column type in CREATE TABLE.
@@ -313,7 +313,7 @@ mod tests {
         let input = "CREATE TABLE users (id INVALID);";
         let mut parser = Parser::new(input);
         let result = parser.parse();
-        assert!(matches!(result, Err(_)));
+        assert!(result.is_err());
     }
 
     // Test SELECT * parsing (2 bonus points).
@@ -325,14 +325,2840 @@ mod tests {
         assert_eq!(
             statement,
             Statement::SelectAll {
-                from: "users".to_string(),
+                into: None,
+                from: TableSource::Table("users".to_string()),
                 r#where: Some(Expression::BinaryOperation {
                     left_operand: Box::new(Expression::Identifier("age".to_string())),
                     operator: BinaryOperator::GreaterThan,
                     right_operand: Box::new(Expression::Number(18)),
                 }),
+                group_by: vec![],
+                having: None,
+                orderby: vec![],
+                limit: None,
+                offset: None,
+            }
+        );
+    }
+
+    // Test two-token lookahead: peek_nth looks ahead without consuming tokens.
+    #[test]
+    fn test_sql_parser_peek_nth_lookahead() {
+        let input = "CREATE TABLE users (id INT);";
+        let mut parser = Parser::new(input);
+        assert_eq!(parser.peek_nth(1), Some(&Token::Keyword(Keyword::Table)));
+        assert_eq!(parser.peek_nth(2), Some(&Token::Identifier("users".to_string())));
+        assert_eq!(parser.peek_nth(0), Some(&Token::Keyword(Keyword::Create)));
+        assert_eq!(parser.peek_nth(100), None); // Past the end of input.
+        assert!(parser.parse().is_ok()); // Peeking did not consume anything.
+    }
+
+    // Test that CREATE must be followed by TABLE (checked with two-token lookahead).
+    #[test]
+    fn test_sql_parser_create_without_table_error() {
+        let input = "CREATE users (id INT);";
+        let mut parser = Parser::new(input);
+        let result = parser.parse();
+        assert!(matches!(result, Err(ParseError::UnexpectedToken(Token::Identifier(_)))));
+    }
+
+    // Test that DEFAULT's expression stops before a following NOT NULL constraint.
+    #[test]
+    fn test_sql_parser_create_table_default_then_not_null() {
+        let input = "CREATE TABLE flags (col BOOL DEFAULT true NOT NULL);";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::CreateTable {
+                table_name: "flags".to_string(),
+                if_not_exists: false,
+                column_list: vec![
+                    TableColumn {
+                        column_name: "col".to_string(),
+                        column_type: DBType::Bool,
+                        collation: None,
+                        comment: None,
+                        span: Span::default(),
+                        constraints: vec![
+                            Constraint::Default(Expression::Bool(true)),
+                            Constraint::NotNull,
+                        ],
+                    },
+                ],
+            }
+        );
+    }
+
+    // Test minimal parenthesization: only parentheses required by precedence are printed.
+    #[test]
+    fn test_pratt_parser_to_sql_minimal_parentheses() {
+        let cases = [
+            ("(a + b) + c", "a + b + c"),
+            ("a + b * c", "a + b * c"),
+            ("(a + b) * c", "(a + b) * c"),
+            ("a - (b - c)", "a - (b - c)"),
+            ("-(a + 1)", "-(a + 1)"),
+            ("- -5", "- -5"),
+            ("NOT (a = 1 OR b = 2) AND c", "NOT (a = 1 OR b = 2) AND c"),
+        ];
+        for (source, expected) in cases {
+            let input = format!("SELECT {} FROM t;", source);
+            let mut parser = Parser::new(&input);
+            match parser.parse().unwrap() {
+                Statement::Select { columns, .. } => assert_eq!(columns[0].to_sql(), expected),
+                other => panic!("Expected SELECT, got {:?}", other),
+            }
+        }
+    }
+
+    // Test Expression::precedence for atoms and operators.
+    #[test]
+    fn test_pratt_parser_expression_precedence() {
+        let atom = Expression::Identifier("a".to_string());
+        let sum = Expression::BinaryOperation {
+            left_operand: Box::new(atom.clone()),
+            operator: BinaryOperator::Plus,
+            right_operand: Box::new(Expression::Number(1)),
+        };
+        let product = Expression::BinaryOperation {
+            left_operand: Box::new(atom.clone()),
+            operator: BinaryOperator::Multiply,
+            right_operand: Box::new(Expression::Number(2)),
+        };
+        assert!(atom.precedence() > product.precedence());
+        assert!(product.precedence() > sum.precedence());
+    }
+
+    // Test trailing commas in a select list: rejected by default, accepted when enabled.
+    #[test]
+    fn test_sql_parser_select_trailing_comma_option() {
+        let input = "SELECT a, b, FROM t;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::From)))));
+
+        let options = ParserOptions { allow_trailing_comma: true, ..ParserOptions::default() };
+        let mut parser = Parser::with_options(input, options);
+        assert_eq!(
+            parser.parse().unwrap(),
+            Statement::Select {
+                columns: vec![
+                    Expression::Identifier("a".to_string()),
+                    Expression::Identifier("b".to_string()),
+                ],
+                into: None,
+                from: TableSource::Table("t".to_string()),
+                r#where: None,
+                group_by: vec![],
+                having: None,
+                orderby: vec![],
+                limit: None,
+                offset: None,
+            }
+        );
+    }
+
+    // Test trailing commas in a CREATE TABLE column list: rejected by default, accepted when enabled.
+    #[test]
+    fn test_sql_parser_create_table_trailing_comma_option() {
+        let input = "CREATE TABLE t (a INT,);";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::RightParentheses))));
+
+        let options = ParserOptions { allow_trailing_comma: true, ..ParserOptions::default() };
+        let mut parser = Parser::with_options(input, options);
+        assert_eq!(
+            parser.parse().unwrap(),
+            Statement::CreateTable {
+                table_name: "t".to_string(),
+                if_not_exists: false,
+                column_list: vec![TableColumn {
+                    column_name: "a".to_string(),
+                    column_type: DBType::Int,
+                    collation: None,
+                    comment: None,
+                    span: Span::default(),
+                    constraints: vec![],
+                }],
+            }
+        );
+    }
+
+    // Test anonymous (?) and positional ($1) bind parameters in a WHERE clause.
+    #[test]
+    fn test_sql_parser_bind_parameters() {
+        let cases = [
+            ("SELECT * FROM t WHERE id = ?;", ParamKind::Anonymous, "SELECT * FROM t WHERE (id = ?)"),
+            ("SELECT * FROM t WHERE id = $1;", ParamKind::Positional(1), "SELECT * FROM t WHERE (id = $1)"),
+        ];
+        for (input, kind, display) in cases {
+            let mut parser = Parser::new(input);
+            let statement = parser.parse().unwrap();
+            assert_eq!(
+                statement,
+                Statement::SelectAll {
+                    into: None,
+                    from: TableSource::Table("t".to_string()),
+                    r#where: Some(Expression::BinaryOperation {
+                        left_operand: Box::new(Expression::Identifier("id".to_string())),
+                        operator: BinaryOperator::Equal,
+                        right_operand: Box::new(Expression::Parameter(kind)),
+                    }),
+                    group_by: vec![],
+                    having: None,
+                    orderby: vec![],
+                    limit: None,
+                    offset: None,
+                }
+            );
+            assert_eq!(statement.to_string(), display); // Display reproduces ? and $1.
+        }
+    }
+
+    // Test that a $ without a position number is rejected by the tokenizer.
+    #[test]
+    fn test_tokenizer_error_positional_param_without_number() {
+        let mut tokenizer = Tokenizer::new("$x");
+        assert!(matches!(tokenizer.tokenize(), Err(TokenizerError::UnexpectedChar('$'))));
+    }
+
+    // Test named bind parameters with both the : and @ prefixes.
+    #[test]
+    fn test_sql_parser_named_parameters() {
+        let cases = [
+            ("SELECT * FROM t WHERE id = :user_id;", ":user_id"),
+            ("SELECT * FROM t WHERE id = @userId;", "@userId"),
+        ];
+        for (input, name) in cases {
+            let mut parser = Parser::new(input);
+            assert_eq!(
+                parser.parse().unwrap(),
+                Statement::SelectAll {
+                    into: None,
+                    from: TableSource::Table("t".to_string()),
+                    r#where: Some(Expression::BinaryOperation {
+                        left_operand: Box::new(Expression::Identifier("id".to_string())),
+                        operator: BinaryOperator::Equal,
+                        right_operand: Box::new(Expression::Parameter(ParamKind::Named(name.to_string()))),
+                    }),
+                    group_by: vec![],
+                    having: None,
+                    orderby: vec![],
+                    limit: None,
+                    offset: None,
+                }
+            );
+        }
+    }
+
+    // Test that a lone : or @ without a name is rejected by the tokenizer.
+    #[test]
+    fn test_tokenizer_error_lone_named_param_prefix() {
+        let mut tokenizer = Tokenizer::new("id = :");
+        assert!(matches!(tokenizer.tokenize(), Err(TokenizerError::UnexpectedChar(':'))));
+        let mut tokenizer = Tokenizer::new("id = @ 1");
+        assert!(matches!(tokenizer.tokenize(), Err(TokenizerError::UnexpectedChar('@'))));
+    }
+
+    // Test collecting anonymous bind parameters from a WHERE clause.
+    #[test]
+    fn test_sql_parser_placeholders_anonymous() {
+        let input = "SELECT * FROM t WHERE a = ? AND b = ?;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(statement.placeholders(), vec![ParamKind::Anonymous, ParamKind::Anonymous]);
+    }
+
+    // Test that named bind parameters are collected in query order across clauses.
+    #[test]
+    fn test_sql_parser_placeholders_named_order() {
+        let input = "SELECT a + :first FROM t WHERE b = @second OR c = :third;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement.placeholders(),
+            vec![
+                ParamKind::Named(":first".to_string()),
+                ParamKind::Named("@second".to_string()),
+                ParamKind::Named(":third".to_string()),
+            ]
+        );
+    }
+
+    // Test a function call whose arguments are arithmetic expressions.
+    #[test]
+    fn test_pratt_parser_function_call_with_expression_args() {
+        let input = "SELECT ROUND(price * 11 / 10, 2) FROM products;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Select {
+                columns: vec![Expression::FunctionCall {
+                    name: "ROUND".to_string(),
+                    distinct: false,
+                    args: vec![
+                        Expression::BinaryOperation {
+                            left_operand: Box::new(Expression::BinaryOperation {
+                                left_operand: Box::new(Expression::Identifier("price".to_string())),
+                                operator: BinaryOperator::Multiply,
+                                right_operand: Box::new(Expression::Number(11)),
+                            }),
+                            operator: BinaryOperator::Divide,
+                            right_operand: Box::new(Expression::Number(10)),
+                        },
+                        Expression::Number(2),
+                    ],
+                    filter: None,
+                }],
+                into: None,
+                from: TableSource::Table("products".to_string()),
+                r#where: None,
+                group_by: vec![],
+                having: None,
+                orderby: vec![],
+                limit: None,
+                offset: None,
+            }
+        );
+        assert_eq!(statement.to_string(), "SELECT ROUND(((price * 11) / 10), 2) FROM products");
+    }
+
+    // Test nested function calls.
+    #[test]
+    fn test_pratt_parser_nested_function_calls() {
+        let input = "SELECT UPPER(TRIM(name)), NOW() FROM users;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Select {
+                columns: vec![
+                    Expression::FunctionCall {
+                        name: "UPPER".to_string(),
+                        distinct: false,
+                        args: vec![Expression::FunctionCall {
+                            name: "TRIM".to_string(),
+                            distinct: false,
+                            args: vec![Expression::Identifier("name".to_string())],
+                            filter: None,
+                        }],
+                        filter: None,
+                    },
+                    Expression::FunctionCall { name: "NOW".to_string(), distinct: false, args: vec![], filter: None },
+                ],
+                into: None,
+                from: TableSource::Table("users".to_string()),
+                r#where: None,
+                group_by: vec![],
+                having: None,
+                orderby: vec![],
+                limit: None,
+                offset: None,
+            }
+        );
+        assert_eq!(statement.to_string(), "SELECT UPPER(TRIM(name)), NOW() FROM users");
+    }
+
+    // Test COLLATE on an ORDER BY expression.
+    #[test]
+    fn test_sql_parser_order_by_collate() {
+        let input = "SELECT name FROM users ORDER BY name COLLATE \"en_US\" DESC;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Select {
+                columns: vec![Expression::Identifier("name".to_string())],
+                into: None,
+                from: TableSource::Table("users".to_string()),
+                r#where: None,
+                group_by: vec![],
+                having: None,
+                orderby: vec![OrderByItem {
+                    expr: Expression::Collate {
+                        expr: Box::new(Expression::Identifier("name".to_string())),
+                        collation: "en_US".to_string(),
+                    },
+                    order: Some(SortOrder::Desc),
+                }],
+                limit: None,
+                offset: None,
+            }
+        );
+    }
+
+    // Test COLLATE on a column definition, alongside a constraint.
+    #[test]
+    fn test_sql_parser_create_table_column_collate() {
+        let input = "CREATE TABLE users (name VARCHAR(50) COLLATE utf8 NOT NULL);";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::CreateTable {
+                table_name: "users".to_string(),
+                if_not_exists: false,
+                column_list: vec![TableColumn {
+                    column_name: "name".to_string(),
+                    column_type: DBType::Varchar(50),
+                    collation: Some("utf8".to_string()),
+                    comment: None,
+                    span: Span::default(),
+                    constraints: vec![Constraint::NotNull],
+                }],
+            }
+        );
+        assert_eq!(statement.to_string(), "CREATE TABLE users (name VARCHAR(50) COLLATE \"utf8\" NOT NULL)");
+    }
+
+    // Test AUTO_INCREMENT (and the AUTOINCREMENT spelling) alongside PRIMARY KEY.
+    #[test]
+    fn test_sql_parser_create_table_auto_increment() {
+        for input in [
+            "CREATE TABLE t (id INT PRIMARY KEY AUTO_INCREMENT);",
+            "CREATE TABLE t (id INT PRIMARY KEY autoincrement);",
+        ] {
+            let mut parser = Parser::new(input);
+            let statement = parser.parse().unwrap();
+            assert_eq!(
+                statement,
+                Statement::CreateTable {
+                    table_name: "t".to_string(),
+                    if_not_exists: false,
+                    column_list: vec![TableColumn {
+                        column_name: "id".to_string(),
+                        column_type: DBType::Int,
+                        collation: None,
+                        comment: None,
+                        span: Span::default(),
+                        constraints: vec![Constraint::PrimaryKey, Constraint::AutoIncrement],
+                    }],
+                }
+            );
+            assert_eq!(statement.to_string(), "CREATE TABLE t (id INT PRIMARY KEY AUTO_INCREMENT)");
+        }
+    }
+
+    // Test map_expression renaming an identifier everywhere in the tree.
+    #[test]
+    fn test_pratt_parser_map_expression_rename_identifier() {
+        let input = "SELECT a + (a * c) FROM t;";
+        let mut parser = Parser::new(input);
+        let expr = match parser.parse().unwrap() {
+            Statement::Select { mut columns, .. } => columns.remove(0),
+            other => panic!("Expected SELECT, got {:?}", other),
+        };
+        let renamed = map_expression(expr, &mut |e| match e {
+            Expression::Identifier(id) if id == "a" => Expression::Identifier("b".to_string()),
+            e => e,
+        });
+        assert_eq!(
+            renamed,
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("b".to_string())),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("b".to_string())),
+                    operator: BinaryOperator::Multiply,
+                    right_operand: Box::new(Expression::Identifier("c".to_string())),
+                }),
+            }
+        );
+    }
+
+    // Test inspecting unconsumed tokens after a parse error.
+    #[test]
+    fn test_sql_parser_remaining_tokens_after_error() {
+        let input = "SELECT name users WHERE age > 1;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Identifier(_)))));
+        assert_eq!(
+            parser.remaining_tokens(),
+            vec![
+                Token::Identifier("users".to_string()),
+                Token::Keyword(Keyword::Where),
+                Token::Identifier("age".to_string()),
+                Token::GreaterThan,
+                Token::Number(1),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+        assert!(parser.remaining_tokens().is_empty()); // Already drained.
+    }
+
+    // Helper: parse "SELECT * FROM t WHERE <condition>;" and return the WHERE expression.
+    fn parse_where(condition: &str) -> Expression {
+        let input = format!("SELECT * FROM t WHERE {};", condition);
+        let mut parser = Parser::new(&input);
+        match parser.parse().unwrap() {
+            Statement::SelectAll { r#where: Some(expr), .. } => expr,
+            other => panic!("Expected SELECT * with WHERE, got {:?}", other),
+        }
+    }
+
+    // Helper: return the column definitions of a parsed CREATE TABLE statement.
+    fn create_table_columns(statement: &Statement) -> &Vec<TableColumn> {
+        match statement {
+            Statement::CreateTable { column_list, .. } => column_list,
+            other => panic!("Expected CREATE TABLE, got {:?}", other),
+        }
+    }
+
+    // Test NOT IN via the NOT-prefix lookahead.
+    #[test]
+    fn test_pratt_parser_not_in() {
+        assert_eq!(
+            parse_where("id NOT IN (1, 2)"),
+            Expression::InList {
+                expr: Box::new(Expression::Identifier("id".to_string())),
+                list: vec![Expression::Number(1), Expression::Number(2)],
+                negated: true,
+            }
+        );
+        assert!(matches!(parse_where("id IN (1)"), Expression::InList { negated: false, .. }));
+    }
+
+    // Test NOT LIKE via the NOT-prefix lookahead.
+    #[test]
+    fn test_pratt_parser_not_like() {
+        assert_eq!(
+            parse_where("name NOT LIKE 'A%'"),
+            Expression::Like {
+                expr: Box::new(Expression::Identifier("name".to_string())),
+                pattern: Box::new(Expression::String("A%".to_string())),
+                negated: true,
+                escape: None,
+            }
+        );
+    }
+
+    // Test NOT BETWEEN via the NOT-prefix lookahead.
+    #[test]
+    fn test_pratt_parser_not_between() {
+        assert_eq!(
+            parse_where("age NOT BETWEEN 18 AND 65"),
+            Expression::Between {
+                expr: Box::new(Expression::Identifier("age".to_string())),
+                low: Box::new(Expression::Number(18)),
+                high: Box::new(Expression::Number(65)),
+                negated: true,
+            }
+        );
+    }
+
+    // Test that NOT in infix position before anything but IN/LIKE/BETWEEN is an error.
+    #[test]
+    fn test_pratt_parser_stray_infix_not_error() {
+        let input = "SELECT a NOT b FROM t;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Not)))));
+    }
+
+    // Test that parse_lenient accepts a statement without a semicolon while parse rejects it.
+    #[test]
+    fn test_sql_parser_lenient_missing_semicolon() {
+        let input = "SELECT 1 FROM t";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Eof))));
+
+        let mut parser = Parser::new(input);
+        assert_eq!(
+            parser.parse_lenient().unwrap(),
+            Statement::Select {
+                columns: vec![Expression::Number(1)],
+                into: None,
+                from: TableSource::Table("t".to_string()),
+                r#where: None,
+                group_by: vec![],
+                having: None,
                 orderby: vec![],
+                limit: None,
+                offset: None,
             }
         );
+
+        // A semicolon is still accepted, and trailing tokens are still an error.
+        let mut parser = Parser::new("SELECT 1 FROM t;");
+        assert!(parser.parse_lenient().is_ok());
+        let mut parser = Parser::new("SELECT 1 FROM t u");
+        assert!(parser.parse_lenient().is_err());
+    }
+
+    // Test that each column records a distinct, ordered source span, and that equality ignores spans.
+    #[test]
+    fn test_sql_parser_create_table_column_spans() {
+        let input = "CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR(10));";
+        let mut parser = Parser::new(input);
+        let column_list = match parser.parse().unwrap() {
+            Statement::CreateTable { column_list, .. } => column_list,
+            other => panic!("Expected CREATE TABLE, got {:?}", other),
+        };
+        let (first, second) = (column_list[0].span, column_list[1].span);
+        assert_eq!(&input[first.start..first.end], "id INT PRIMARY KEY");
+        assert_eq!(&input[second.start..second.end], "name VARCHAR(10)");
+        assert!(first.end < second.start);
+        assert_eq!(
+            column_list[0],
+            TableColumn {
+                column_name: "id".to_string(),
+                column_type: DBType::Int,
+                collation: None,
+                comment: None,
+                span: Span::default(),
+                constraints: vec![Constraint::PrimaryKey],
+            }
+        );
+    }
+
+    // Test that tokenize_with_spans reports byte ranges for each token.
+    #[test]
+    fn test_tokenizer_spans() {
+        let mut tokenizer = Tokenizer::new("SELECT 'é' >= 10");
+        let tokens = tokenizer.tokenize_with_spans().unwrap();
+        let spans: Vec<(usize, usize)> = tokens.iter().map(|(_, span)| (span.start, span.end)).collect();
+        assert_eq!(spans, vec![(0, 6), (7, 11), (12, 14), (15, 17), (17, 17)]);
+    }
+
+    // Test a SELECT using every optional clause in canonical order.
+    #[test]
+    fn test_sql_parser_select_all_clauses_in_order() {
+        let input = "SELECT country, COUNT(id) FROM users WHERE age > 18 GROUP BY country HAVING COUNT(id) > 10 ORDER BY country LIMIT 5 OFFSET 10;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        let count_id = Expression::FunctionCall {
+            name: "COUNT".to_string(),
+            distinct: false,
+            args: vec![Expression::Identifier("id".to_string())],
+            filter: None,
+        };
+        assert_eq!(
+            statement,
+            Statement::Select {
+                columns: vec![Expression::Identifier("country".to_string()), count_id.clone()],
+                into: None,
+                from: TableSource::Table("users".to_string()),
+                r#where: Some(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("age".to_string())),
+                    operator: BinaryOperator::GreaterThan,
+                    right_operand: Box::new(Expression::Number(18)),
+                }),
+                group_by: vec![GroupByKind::Expression(Expression::Identifier("country".to_string()))],
+                having: Some(Expression::BinaryOperation {
+                    left_operand: Box::new(count_id),
+                    operator: BinaryOperator::GreaterThan,
+                    right_operand: Box::new(Expression::Number(10)),
+                }),
+                orderby: vec![OrderByItem {
+                    expr: Expression::Identifier("country".to_string()),
+                    order: None,
+                }],
+                limit: Some(Limit::Count(5)),
+                offset: Some(10),
+            }
+        );
+        assert_eq!(
+            statement.to_string(),
+            "SELECT country, COUNT(id) FROM users WHERE (age > 18) GROUP BY country HAVING (COUNT(id) > 10) ORDER BY country LIMIT 5 OFFSET 10"
+        );
+    }
+
+    // Test that a clause out of canonical order (GROUP BY after ORDER BY) is rejected.
+    #[test]
+    fn test_sql_parser_select_clause_out_of_order_error() {
+        let input = "SELECT x FROM t ORDER BY x GROUP BY y;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Group)))));
+    }
+
+    // Test DISTINCT inside an aggregate call, and the same call without it.
+    #[test]
+    fn test_pratt_parser_count_distinct() {
+        for (input, distinct) in [
+            ("SELECT COUNT(DISTINCT country) FROM users;", true),
+            ("SELECT COUNT(country) FROM users;", false),
+        ] {
+            let mut parser = Parser::new(input);
+            let statement = parser.parse().unwrap();
+            match &statement {
+                Statement::Select { columns, .. } => assert_eq!(
+                    columns[0],
+                    Expression::FunctionCall {
+                        name: "COUNT".to_string(),
+                        distinct,
+                        args: vec![Expression::Identifier("country".to_string())],
+                        filter: None,
+                    }
+                ),
+                other => panic!("Expected SELECT, got {:?}", other),
+            }
+            assert_eq!(statement.to_string(), input.trim_end_matches(';'));
+        }
+    }
+
+    // Test that DISTINCT without an argument is an error.
+    #[test]
+    fn test_pratt_parser_count_distinct_without_argument_error() {
+        let mut parser = Parser::new("SELECT COUNT(DISTINCT) FROM users;");
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::RightParentheses))));
+    }
+
+    // Test CREATE TABLE with and without IF NOT EXISTS.
+    #[test]
+    fn test_sql_parser_create_table_if_not_exists() {
+        for (input, expected) in [
+            ("CREATE TABLE IF NOT EXISTS users (id INT);", true),
+            ("CREATE TABLE users (id INT);", false),
+        ] {
+            let mut parser = Parser::new(input);
+            let statement = parser.parse().unwrap();
+            assert!(matches!(statement, Statement::CreateTable { if_not_exists, .. } if if_not_exists == expected));
+            assert_eq!(statement.to_string(), input.trim_end_matches(';'));
+        }
+    }
+
+    // Test that the wrong form CREATE TABLE IF EXISTS is rejected.
+    #[test]
+    fn test_sql_parser_create_table_if_exists_error() {
+        let mut parser = Parser::new("CREATE TABLE IF EXISTS users (id INT);");
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Exists)))));
+    }
+
+    // Test that line and block comments are skipped by the default tokenizer.
+    #[test]
+    fn test_tokenizer_skips_comments() {
+        let input = "SELECT a -- trailing comment\n/* block */ FROM t";
+        let mut tokenizer = Tokenizer::new(input);
+        assert_eq!(
+            tokenizer.tokenize().unwrap(),
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::Identifier("a".to_string()),
+                Token::Keyword(Keyword::From),
+                Token::Identifier("t".to_string()),
+                Token::Eof,
+            ]
+        );
+        let mut tokenizer = Tokenizer::new("SELECT /* never closed");
+        assert!(matches!(tokenizer.tokenize(), Err(TokenizerError::UnterminatedComment)));
+    }
+
+    // Test that tokenize_with_trivia attaches comment text to the following token.
+    #[test]
+    fn test_tokenizer_with_trivia_preserves_comments() {
+        let input = "SELECT a -- pick a\nFROM t;";
+        let mut tokenizer = Tokenizer::new(input);
+        let tokens = tokenizer.tokenize_with_trivia().unwrap();
+        let from = tokens.iter().find(|t| t.token == Token::Keyword(Keyword::From)).unwrap();
+        assert_eq!(from.leading_trivia, " -- pick a\n");
+        assert_eq!(tokens[0].leading_trivia, "");
+        // Concatenating trivia and token text reproduces the input exactly.
+        let rebuilt: String = tokens
+            .iter()
+            .map(|t| format!("{}{}", t.leading_trivia, &input[t.span.start..t.span.end]))
+            .collect();
+        assert_eq!(rebuilt, input);
+    }
+
+    // Test SQL-standard quote escaping by doubling inside string literals.
+    #[test]
+    fn test_tokenizer_doubled_quote_escape() {
+        let cases = [
+            ("'it''s'", "it's"),
+            ("'a'''", "a'"),
+            ("''", ""),
+            (r#""say ""hi""""#, r#"say "hi""#),
+        ];
+        for (input, expected) in cases {
+            let mut tokenizer = Tokenizer::new(input);
+            assert_eq!(tokenizer.tokenize().unwrap(), vec![Token::String(expected.to_string()), Token::Eof]);
+        }
+        let mut tokenizer = Tokenizer::new("'a''");
+        assert!(matches!(tokenizer.tokenize(), Err(TokenizerError::UnterminatedString)));
+    }
+
+    // Test ORDER BY ordinals parse as numeric keys and validate against the select list.
+    #[test]
+    fn test_sql_parser_order_by_ordinal() {
+        let input = "SELECT name, age FROM users ORDER BY 2 DESC, 1;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        let Statement::Select { orderby, .. } = &statement else {
+            panic!("expected SELECT, got {:?}", statement);
+        };
+        let ordinals: Vec<Option<u64>> = orderby.iter().map(|item| item.ordinal()).collect();
+        assert_eq!(ordinals, vec![Some(2), Some(1)]);
+        assert_eq!(orderby[0].order, Some(SortOrder::Desc));
+        assert_eq!(orderby[1].order, None);
+        assert_eq!(statement.validate(), Ok(()));
+    }
+
+    // Test an ORDER BY ordinal beyond the select list fails validation.
+    #[test]
+    fn test_sql_parser_order_by_ordinal_out_of_range() {
+        let input = "SELECT name, age FROM users ORDER BY 3;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement.validate(),
+            Err(ValidationError::OrdinalOutOfRange { ordinal: 3, column_count: 2 })
+        );
+
+        let input = "SELECT * FROM users ORDER BY 0;";
+        let mut parser = Parser::new(input);
+        assert!(parser.parse().unwrap().validate().is_err());
+    }
+
+    // Test try_parse returns an error, never a panic, for adversarial input.
+    #[test]
+    fn test_sql_parser_try_parse_adversarial_inputs() {
+        let deep_parens = format!("SELECT {}1{} FROM t;", "(".repeat(10_000), ")".repeat(10_000));
+        let deep_unary = format!("SELECT {}1 FROM t;", "-".repeat(10_000));
+        let inputs = [
+            "",
+            ";",
+            "+ - * / = < >",
+            "SELECT",
+            "SELECT * FROM",
+            "SELECT 99999999999999999999999 FROM t;",
+            "SELECT ((a FROM t;",
+            "SELECT a) FROM t;",
+            "'",
+            "SELECT 'abc FROM t;",
+            "/* never closed",
+            "SELECT # FROM t;",
+            "CREATE TABLE t (a VARCHAR(99999999999999999999));",
+            "CREATE TABLE",
+            "SELECT a FROM t ORDER BY",
+            &deep_parens,
+            &deep_unary,
+        ];
+        for input in inputs {
+            assert!(try_parse(input).is_err(), "expected error for {:?}", input);
+        }
+        assert!(matches!(
+            try_parse("SELECT 99999999999999999999999 FROM t;"),
+            Err(ParseError::Tokenizer(TokenizerError::NumberTooLarge(_)))
+        ));
+        assert!(matches!(try_parse(&deep_parens), Err(ParseError::DepthLimitExceeded)));
+        assert!(try_parse("SELECT a FROM t;").is_ok());
+    }
+
+    // Test a comparison applied to an unparenthesized comparison is rejected.
+    #[test]
+    fn test_pratt_parser_chained_comparison_rejected() {
+        let input = "SELECT * FROM t WHERE a < b < c;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::ChainedComparison(Token::LessThan))));
+
+        let input = "SELECT * FROM t WHERE a = b != c;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::ChainedComparison(Token::NotEqual))));
+    }
+
+    // Test parentheses make a comparison of comparisons explicit.
+    #[test]
+    fn test_pratt_parser_parenthesized_comparison_chain_allowed() {
+        assert_eq!(
+            parse_where("(a < b) = c"),
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("a".to_string())),
+                    operator: BinaryOperator::LessThan,
+                    right_operand: Box::new(Expression::Identifier("b".to_string())),
+                }),
+                operator: BinaryOperator::Equal,
+                right_operand: Box::new(Expression::Identifier("c".to_string())),
+            }
+        );
+        // Comparisons joined by AND are not chains.
+        assert!(matches!(parse_where("a < b AND b < c"), Expression::BinaryOperation { operator: BinaryOperator::And, .. }));
+    }
+
+    // Test DATE and TIMESTAMP typed literals in WHERE comparisons.
+    #[test]
+    fn test_pratt_parser_typed_literals() {
+        assert_eq!(
+            parse_where("created >= DATE '2020-01-01'"),
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("created".to_string())),
+                operator: BinaryOperator::GreaterThanOrEqual,
+                right_operand: Box::new(Expression::TypedLiteral {
+                    ty: DBType::Date,
+                    value: "2020-01-01".to_string(),
+                }),
+            }
+        );
+        let expr = parse_where("updated < TIMESTAMP '2020-01-01 00:00:00'");
+        assert_eq!(expr.to_string(), "(updated < TIMESTAMP \"2020-01-01 00:00:00\")");
+
+        let input = "SELECT * FROM t WHERE d = DATE '';";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::InvalidTypedLiteral(DBType::Date))));
+    }
+
+    // Test the parser accepts a hand-built token stream.
+    #[test]
+    fn test_sql_parser_from_tokens() {
+        let tokens = vec![
+            Token::Keyword(Keyword::Select),
+            Token::Number(1),
+            Token::Keyword(Keyword::From),
+            Token::Identifier("t".to_string()),
+            Token::Semicolon,
+            Token::Eof,
+        ];
+        let mut parser = Parser::from_tokens(tokens);
+        assert_eq!(
+            parser.parse().unwrap(),
+            Statement::Select {
+                columns: vec![Expression::Number(1)],
+                into: None,
+                from: TableSource::Table("t".to_string()),
+                r#where: None,
+                group_by: vec![],
+                having: None,
+                orderby: vec![],
+                limit: None,
+                offset: None,
+            }
+        );
+    }
+
+    // Test string literals with quotes and backslashes round-trip through to_sql.
+    #[test]
+    fn test_pratt_parser_string_literal_to_sql_round_trip() {
+        let expr = parse_where(r"note = 'it''s a \\ path'");
+        let Expression::BinaryOperation { right_operand, .. } = &expr else {
+            panic!("expected comparison, got {:?}", expr);
+        };
+        assert_eq!(**right_operand, Expression::String(r"it's a \ path".to_string()));
+        let sql = expr.to_sql();
+        assert_eq!(sql, r"note = 'it''s a \\ path'");
+        assert_eq!(parse_where(&sql), expr);
+    }
+
+    // Test a single WITH common table expression feeding a SELECT.
+    #[test]
+    fn test_sql_parser_with_single_cte() {
+        let input = "WITH active AS (SELECT * FROM users WHERE active) SELECT name FROM active;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::WithQuery {
+                ctes: vec![Cte {
+                    name: "active".to_string(),
+                    query: Box::new(Statement::SelectAll {
+                        into: None,
+                        from: TableSource::Table("users".to_string()),
+                        r#where: Some(Expression::Identifier("active".to_string())),
+                        group_by: vec![],
+                        having: None,
+                        orderby: vec![],
+                        limit: None,
+                        offset: None,
+                    }),
+                }],
+                body: Box::new(Statement::Select {
+                    columns: vec![Expression::Identifier("name".to_string())],
+                    into: None,
+                    from: TableSource::Table("active".to_string()),
+                    r#where: None,
+                    group_by: vec![],
+                    having: None,
+                    orderby: vec![],
+                    limit: None,
+                    offset: None,
+                }),
+            }
+        );
+        assert_eq!(
+            statement.to_string(),
+            "WITH active AS (SELECT * FROM users WHERE active) SELECT name FROM active"
+        );
+    }
+
+    // Test SQL-standard OFFSET ... ROWS FETCH NEXT ... ROWS ONLY pagination.
+    #[test]
+    fn test_sql_parser_offset_fetch_pagination() {
+        let input = "SELECT name FROM users ORDER BY name OFFSET 10 ROWS FETCH NEXT 5 ROWS ONLY;";
+        let mut parser = Parser::new(input);
+        let ansi = parser.parse().unwrap();
+        let Statement::Select { limit, offset, .. } = &ansi else {
+            panic!("expected SELECT, got {:?}", ansi);
+        };
+        assert_eq!((limit.clone(), *offset), (Some(Limit::Count(5)), Some(10)));
+
+        // Both spellings produce identical statements.
+        let input = "SELECT name FROM users ORDER BY name LIMIT 5 OFFSET 10;";
+        let mut parser = Parser::new(input);
+        assert_eq!(parser.parse().unwrap(), ansi);
+
+        // LIMIT and FETCH cannot both be given.
+        let input = "SELECT name FROM users LIMIT 5 FETCH NEXT 5 ROWS ONLY;";
+        let mut parser = Parser::new(input);
+        assert!(parser.parse().is_err());
+    }
+
+    // Test non-reserved keywords are accepted as identifiers only when the option is enabled.
+    #[test]
+    fn test_sql_parser_keywords_as_identifiers() {
+        assert!(!Keyword::Key.is_reserved());
+        assert!(Keyword::Select.is_reserved());
+
+        let input = "SELECT key FROM t;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Key)))));
+
+        let options = ParserOptions { allow_keywords_as_identifiers: true, ..ParserOptions::default() };
+        let mut parser = Parser::with_options("SELECT key, date FROM int WHERE date > DATE '2020-01-01';", options.clone());
+        let Statement::Select { columns, from, r#where, .. } = parser.parse().unwrap() else {
+            panic!("expected SELECT");
+        };
+        assert_eq!(columns, vec![Expression::Identifier("key".to_string()), Expression::Identifier("date".to_string())]);
+        assert_eq!(from, TableSource::Table("int".to_string()));
+        assert!(matches!(
+            r#where,
+            Some(Expression::BinaryOperation { right_operand, .. }) if matches!(*right_operand, Expression::TypedLiteral { .. })
+        ));
+
+        // Only DATE and TIMESTAMP stay keywords before a string; KEY becomes a name, so the string is the error.
+        let mut parser = Parser::with_options("SELECT key 'x' FROM t;", options.clone());
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::String(_)))));
+
+        // Reserved keywords stay keywords even in permissive mode.
+        let mut parser = Parser::with_options("SELECT from FROM t;", options);
+        assert!(parser.parse().is_err());
+    }
+
+    // Test UPDATE assignments accept full expressions referencing columns.
+    #[test]
+    fn test_sql_parser_update_set_expression() {
+        let input = "UPDATE t SET count = count + 1, name = 'x' WHERE id = 1;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Update {
+                table_name: "t".to_string(),
+                assignments: vec![
+                    Assignment {
+                        column: "count".to_string(),
+                        value: Expression::BinaryOperation {
+                            left_operand: Box::new(Expression::Identifier("count".to_string())),
+                            operator: BinaryOperator::Plus,
+                            right_operand: Box::new(Expression::Number(1)),
+                        },
+                    },
+                    Assignment {
+                        column: "name".to_string(),
+                        value: Expression::String("x".to_string()),
+                    },
+                ],
+                r#where: Some(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("id".to_string())),
+                    operator: BinaryOperator::Equal,
+                    right_operand: Box::new(Expression::Number(1)),
+                }),
+                returning: vec![],
+            }
+        );
+        assert_eq!(statement.to_string(), "UPDATE t SET count = (count + 1), name = \"x\" WHERE (id = 1)");
+    }
+
+    // Test the statement span covers the first keyword through the semicolon.
+    #[test]
+    fn test_sql_parser_statement_span() {
+        let input = "SELECT name FROM users WHERE id = 1;";
+        let mut parser = Parser::new(input);
+        let Spanned { node, span } = parser.parse_spanned().unwrap();
+        assert!(matches!(node, Statement::Select { .. }));
+        assert_eq!(span, Span { start: 0, end: input.len() });
+
+        // Surrounding whitespace and comments are not part of the statement.
+        let input = "  /* lead */ CREATE TABLE t (a INT);  ";
+        let mut parser = Parser::new(input);
+        let span = parser.parse_spanned().unwrap().span;
+        assert_eq!(&input[span.start..span.end], "CREATE TABLE t (a INT);");
+
+        assert_eq!(Span { start: 4, end: 6 }.merge(Span { start: 1, end: 5 }), Span { start: 1, end: 6 });
+    }
+
+    // Test select-list aliases with AS and, when enabled, without it.
+    #[test]
+    fn test_sql_parser_select_alias() {
+        let years = Expression::Alias {
+            expr: Box::new(Expression::Identifier("age".to_string())),
+            alias: "years".to_string(),
+        };
+        let input = "SELECT age AS years, name FROM users;";
+        let mut parser = Parser::new(input);
+        let Statement::Select { columns, .. } = parser.parse().unwrap() else {
+            panic!("expected SELECT");
+        };
+        assert_eq!(columns, vec![years.clone(), Expression::Identifier("name".to_string())]);
+
+        // Without the option, a bare identifier after an expression is an error.
+        let input = "SELECT age years FROM users;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Identifier(_)))));
+
+        let options = ParserOptions { allow_implicit_alias: true, ..ParserOptions::default() };
+        let mut parser = Parser::with_options("SELECT age years, name FROM users;", options.clone());
+        let Statement::Select { columns, .. } = parser.parse().unwrap() else {
+            panic!("expected SELECT");
+        };
+        assert_eq!(columns, vec![years, Expression::Identifier("name".to_string())]);
+
+        // Only directly before a comma or FROM.
+        let mut parser = Parser::with_options("SELECT age years other FROM users;", options);
+        assert!(parser.parse().is_err());
+    }
+
+    // Test ORDER BY items resolve to aliases, ordinals, or standalone expressions.
+    #[test]
+    fn test_sql_parser_resolve_order_by() {
+        let input = "SELECT name, age AS years FROM users ORDER BY years, 1, age + 1, name;";
+        let mut parser = Parser::new(input);
+        assert_eq!(
+            parser.parse().unwrap().resolve_order_by(),
+            vec![
+                OrderByResolution::Alias { name: "years".to_string(), index: 1 },
+                OrderByResolution::Ordinal(1),
+                OrderByResolution::Expression,
+                OrderByResolution::Expression,
+            ]
+        );
+    }
+
+    // Test quantified comparisons against a subquery.
+    #[test]
+    fn test_pratt_parser_quantified_comparisons() {
+        let subquery = Statement::Select {
+            columns: vec![Expression::Identifier("price".to_string())],
+            into: None,
+            from: TableSource::Table("t".to_string()),
+            r#where: None,
+            group_by: vec![],
+            having: None,
+            orderby: vec![],
+            limit: None,
+            offset: None,
+        };
+        assert_eq!(
+            parse_where("price > ALL (SELECT price FROM t)"),
+            Expression::Quantified {
+                expr: Box::new(Expression::Identifier("price".to_string())),
+                op: BinaryOperator::GreaterThan,
+                quantifier: Quantifier::All,
+                subquery: Box::new(subquery),
+            }
+        );
+        let expr = parse_where("id = ANY (SELECT owner FROM pets WHERE age > 3)");
+        assert!(matches!(
+            &expr,
+            Expression::Quantified { op: BinaryOperator::Equal, quantifier: Quantifier::Any, .. }
+        ));
+        assert_eq!(expr.to_string(), "(id = ANY (SELECT owner FROM pets WHERE (age > 3)))");
+        assert_eq!(expr.to_sql(), "id = ANY (SELECT owner FROM pets WHERE age > 3)");
+    }
+
+    // Test simplify folds double negations and constant comparisons.
+    #[test]
+    fn test_pratt_parser_simplify() {
+        let x = Expression::Identifier("x".to_string());
+        assert_eq!(simplify(parse_where("NOT (NOT x)")), x);
+        assert_eq!(simplify(parse_where("- -x = 1")).to_sql(), "x = 1");
+        assert_eq!(simplify(parse_where("NOT NOT NOT x")).to_sql(), "NOT x");
+        assert_eq!(simplify(parse_where("2 > 1")), Expression::Bool(true));
+        assert_eq!(simplify(parse_where("a = 1 AND 'a' = 'b'")).to_sql(), "a = 1 AND FALSE");
+        // Non-trivial expressions are left untouched.
+        assert_eq!(simplify(parse_where("a > 1 + 2")), parse_where("a > 1 + 2"));
+    }
+
+    // Test LIMIT ALL and LIMIT n produce distinct limit values.
+    #[test]
+    fn test_sql_parser_limit_all() {
+        let input = "SELECT * FROM t LIMIT ALL;";
+        let mut parser = Parser::new(input);
+        let all = parser.parse().unwrap();
+        assert!(matches!(&all, Statement::SelectAll { limit: Some(Limit::All), .. }));
+        assert_eq!(all.to_string(), "SELECT * FROM t LIMIT ALL");
+
+        let input = "SELECT * FROM t LIMIT 5;";
+        let mut parser = Parser::new(input);
+        let five = parser.parse().unwrap();
+        assert!(matches!(&five, Statement::SelectAll { limit: Some(Limit::Count(5)), .. }));
+        assert_ne!(all, five);
+    }
+
+    // Test INSERT with VALUES, DEFAULT VALUES, and SELECT sources.
+    #[test]
+    fn test_sql_parser_insert_sources() {
+        let input = "INSERT INTO t (a, b) VALUES (1, 'x'), (2, 'y');";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Insert {
+                table_name: "t".to_string(),
+                columns: vec!["a".to_string(), "b".to_string()],
+                source: InsertSource::Values(vec![
+                    vec![Expression::Number(1), Expression::String("x".to_string())],
+                    vec![Expression::Number(2), Expression::String("y".to_string())],
+                ]),
+                on_conflict: None,
+                returning: vec![],
+            }
+        );
+        assert_eq!(statement.to_string(), "INSERT INTO t (a, b) VALUES (1, \"x\"), (2, \"y\")");
+
+        let input = "INSERT INTO t DEFAULT VALUES;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Insert {
+                table_name: "t".to_string(),
+                columns: vec![],
+                source: InsertSource::DefaultValues,
+                on_conflict: None,
+                returning: vec![],
+            }
+        );
+        assert_eq!(statement.to_string(), "INSERT INTO t DEFAULT VALUES");
+
+        let input = "INSERT INTO t (a, b) SELECT a, b FROM src;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert!(matches!(
+            &statement,
+            Statement::Insert { source: InsertSource::Query(query), .. } if matches!(**query, Statement::Select { .. })
+        ));
+        assert_eq!(statement.to_string(), "INSERT INTO t (a, b) SELECT a, b FROM src");
+    }
+
+    // Test a parenthesized single expression stays scalar while a comma makes a list.
+    #[test]
+    fn test_pratt_parser_parenthesized_list() {
+        let input = "SELECT (1), (1, 2) FROM t;";
+        let mut parser = Parser::new(input);
+        let Statement::Select { columns, .. } = parser.parse().unwrap() else {
+            panic!("expected SELECT");
+        };
+        assert_eq!(
+            columns,
+            vec![
+                Expression::Number(1),
+                Expression::List(vec![Expression::Number(1), Expression::Number(2)]),
+            ]
+        );
+        assert_eq!(columns[1].to_string(), "(1, 2)");
+
+        let expr = parse_where("(a, b) = (1, 2)");
+        assert_eq!(expr.to_sql(), "(a, b) = (1, 2)");
+    }
+
+    // Test RETURNING lists on INSERT, UPDATE, and DELETE.
+    #[test]
+    fn test_sql_parser_returning_clause() {
+        let input = "INSERT INTO t (a) VALUES (1) RETURNING id, a;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert!(matches!(
+            &statement,
+            Statement::Insert { returning, .. }
+                if *returning == vec![Expression::Identifier("id".to_string()), Expression::Identifier("a".to_string())]
+        ));
+        assert_eq!(statement.to_string(), "INSERT INTO t (a) VALUES (1) RETURNING id, a");
+
+        let input = "DELETE FROM t WHERE a = 1 RETURNING *;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Delete {
+                table_name: "t".to_string(),
+                r#where: Some(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("a".to_string())),
+                    operator: BinaryOperator::Equal,
+                    right_operand: Box::new(Expression::Number(1)),
+                }),
+                returning: vec![Expression::Wildcard],
+            }
+        );
+        assert_eq!(statement.to_string(), "DELETE FROM t WHERE (a = 1) RETURNING *");
+
+        let input = "UPDATE t SET a = 2 RETURNING *;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse().unwrap(), Statement::Update { returning, .. } if returning == vec![Expression::Wildcard]));
+    }
+
+    // Test CREATE TABLE parses identically after the keyword-helper refactor.
+    #[test]
+    fn test_sql_parser_create_table_unchanged_by_keyword_helpers() {
+        let input = "CREATE TABLE IF NOT EXISTS users (id INT PRIMARY KEY, name VARCHAR(50) NOT NULL);";
+        let mut parser = Parser::new(input);
+        assert_eq!(
+            parser.parse().unwrap(),
+            Statement::CreateTable {
+                table_name: "users".to_string(),
+                if_not_exists: true,
+                column_list: vec![
+                    TableColumn {
+                        column_name: "id".to_string(),
+                        column_type: DBType::Int,
+                        collation: None,
+                        comment: None,
+                        constraints: vec![Constraint::PrimaryKey],
+                        span: Span::default(),
+                    },
+                    TableColumn {
+                        column_name: "name".to_string(),
+                        column_type: DBType::Varchar(50),
+                        collation: None,
+                        comment: None,
+                        constraints: vec![Constraint::NotNull],
+                        span: Span::default(),
+                    },
+                ],
+            }
+        );
+    }
+
+    // Test LIKE with and without an ESCAPE character.
+    #[test]
+    fn test_pratt_parser_like_escape() {
+        assert_eq!(
+            parse_where(r"name LIKE '10\\%' ESCAPE '\\'"),
+            Expression::Like {
+                expr: Box::new(Expression::Identifier("name".to_string())),
+                pattern: Box::new(Expression::String(r"10\%".to_string())),
+                negated: false,
+                escape: Some('\\'),
+            }
+        );
+        let expr = parse_where("name LIKE '10!%' ESCAPE '!'");
+        assert_eq!(expr.to_string(), "(name LIKE \"10!%\" ESCAPE \"!\")");
+        assert_eq!(expr.to_sql(), "name LIKE '10!%' ESCAPE '!'");
+        assert!(matches!(parse_where("name LIKE 'A%'"), Expression::Like { escape: None, .. }));
+
+        let input = "SELECT * FROM t WHERE name LIKE 'A%' ESCAPE '!!';";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::InvalidEscape(s)) if s == "!!"));
+    }
+
+    // Test read-only classification of SELECT, UNION, CTE, and INSERT statements.
+    #[test]
+    fn test_sql_parser_is_read_only() {
+        let parse = |input: &str| Parser::new(input).parse().unwrap();
+        assert!(parse("SELECT a FROM t;").is_read_only());
+
+        let union = parse("SELECT a FROM t UNION ALL SELECT a FROM u UNION SELECT * FROM v;");
+        assert!(matches!(&union, Statement::Union { all: false, left, .. } if matches!(**left, Statement::Union { all: true, .. })));
+        assert_eq!(union.to_string(), "SELECT a FROM t UNION ALL SELECT a FROM u UNION SELECT * FROM v");
+        assert!(union.is_read_only());
+
+        assert!(parse("WITH x AS (SELECT * FROM t) SELECT a FROM x;").is_read_only());
+        assert!(!parse("INSERT INTO t (a) VALUES (1);").is_read_only());
+        assert!(!parse("INSERT INTO t SELECT * FROM u;").is_read_only());
+    }
+
+    // Test standalone single- and multi-row VALUES statements.
+    #[test]
+    fn test_sql_parser_values_statement() {
+        let input = "VALUES (1, 'a');";
+        let mut parser = Parser::new(input);
+        assert_eq!(
+            parser.parse().unwrap(),
+            Statement::Values(vec![vec![Expression::Number(1), Expression::String("a".to_string())]])
+        );
+
+        let input = "VALUES (1, 'a'), (2, 'b');";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Values(vec![
+                vec![Expression::Number(1), Expression::String("a".to_string())],
+                vec![Expression::Number(2), Expression::String("b".to_string())],
+            ])
+        );
+        assert_eq!(statement.to_string(), "VALUES (1, \"a\"), (2, \"b\")");
+    }
+
+    // Test unary minus binds looser than ^ but still nests and negates exponents.
+    #[test]
+    fn test_pratt_parser_unary_minus_vs_exponent() {
+        let power = |left: Expression, right: Expression| Expression::BinaryOperation {
+            left_operand: Box::new(left),
+            operator: BinaryOperator::Power,
+            right_operand: Box::new(right),
+        };
+        let negate = |operand: Expression| Expression::UnaryOperation {
+            operator: UnaryOperator::Minus,
+            operand: Box::new(operand),
+        };
+        let parse_column = |sql: &str| {
+            let input = format!("SELECT {} FROM t;", sql);
+            let mut parser = Parser::new(&input);
+            match parser.parse().unwrap() {
+                Statement::Select { mut columns, .. } => columns.remove(0),
+                other => panic!("expected SELECT, got {:?}", other),
+            }
+        };
+        assert_eq!(parse_column("-2 ^ 2"), negate(power(Expression::Number(2), Expression::Number(2))));
+        assert_eq!(parse_column("2 ^ -2"), power(Expression::Number(2), negate(Expression::Number(2))));
+        assert_eq!(parse_column("- -2"), negate(negate(Expression::Number(2))));
+        assert_eq!(parse_column("(-2) ^ 2").to_sql(), "(-2) ^ 2");
+        assert_eq!(parse_column("-2 ^ 2").to_sql(), "-2 ^ 2");
+    }
+
+    // Test one tokenizer instance can be reset and reused for different inputs.
+    #[test]
+    fn test_tokenizer_reset_reuse() {
+        let mut tokenizer = Tokenizer::new("SELECT a FROM t;");
+        assert_eq!(
+            tokenizer.tokenize_buffered().unwrap(),
+            &[
+                Token::Keyword(Keyword::Select),
+                Token::Identifier("a".to_string()),
+                Token::Keyword(Keyword::From),
+                Token::Identifier("t".to_string()),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+        tokenizer.reset("1 + 2");
+        assert_eq!(
+            tokenizer.tokenize_buffered().unwrap(),
+            &[Token::Number(1), Token::Plus, Token::Number(2), Token::Eof]
+        );
+        tokenizer.reset("'x'");
+        assert_eq!(tokenizer.tokenize().unwrap(), vec![Token::String("x".to_string()), Token::Eof]);
+    }
+
+    // Test /*+ */ hint comments become tokens while ordinary comments are dropped.
+    #[test]
+    fn test_tokenizer_hint_comments() {
+        let input = "SELECT /*+ INDEX(t) */ a /* plain */ FROM t;";
+        let mut tokenizer = Tokenizer::new(input);
+        assert_eq!(
+            tokenizer.tokenize().unwrap(),
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::Hint("INDEX(t)".to_string()),
+                Token::Identifier("a".to_string()),
+                Token::Keyword(Keyword::From),
+                Token::Identifier("t".to_string()),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+        // The parser skips hints.
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse().unwrap(), Statement::Select { .. }));
+    }
+
+    // Test BETWEEN takes only the first AND; the second AND is the boolean connective.
+    #[test]
+    fn test_pratt_parser_between_and_capture() {
+        assert_eq!(
+            parse_where("a BETWEEN 1 AND 2 AND b = 3"),
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Between {
+                    expr: Box::new(Expression::Identifier("a".to_string())),
+                    low: Box::new(Expression::Number(1)),
+                    high: Box::new(Expression::Number(2)),
+                    negated: false,
+                }),
+                operator: BinaryOperator::And,
+                right_operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("b".to_string())),
+                    operator: BinaryOperator::Equal,
+                    right_operand: Box::new(Expression::Number(3)),
+                }),
+            }
+        );
+    }
+
+    // Test CREATE TABLE ... AS SELECT alongside the column-list form.
+    #[test]
+    fn test_sql_parser_create_table_as_select() {
+        let input = "CREATE TABLE summary AS SELECT country, COUNT(*) FROM users GROUP BY country;";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::CreateTableAs {
+                name: "summary".to_string(),
+                query: Box::new(Statement::Select {
+                    columns: vec![
+                        Expression::Identifier("country".to_string()),
+                        Expression::FunctionCall {
+                            name: "COUNT".to_string(),
+                            distinct: false,
+                            args: vec![Expression::Wildcard],
+                            filter: None,
+                        },
+                    ],
+                    into: None,
+                    from: TableSource::Table("users".to_string()),
+                    r#where: None,
+                    group_by: vec![GroupByKind::Expression(Expression::Identifier("country".to_string()))],
+                    having: None,
+                    orderby: vec![],
+                    limit: None,
+                    offset: None,
+                }),
+            }
+        );
+        assert_eq!(
+            statement.to_string(),
+            "CREATE TABLE summary AS SELECT country, COUNT(*) FROM users GROUP BY country"
+        );
+
+        let input = "CREATE TABLE summary (country VARCHAR(20));";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse().unwrap(), Statement::CreateTable { .. }));
+    }
+
+    // Test == tokenizes as one Equal only when allow_double_equals is set.
+    #[test]
+    fn test_tokenizer_double_equals_option() {
+        let mut tokenizer = Tokenizer::new("a == b");
+        assert_eq!(
+            tokenizer.tokenize().unwrap(),
+            vec![Token::Identifier("a".to_string()), Token::Equal, Token::Equal, Token::Identifier("b".to_string()), Token::Eof]
+        );
+        let input = "SELECT * FROM t WHERE a == b;";
+        let mut parser = Parser::new(input);
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Equal))));
+
+        let options = TokenizerOptions { allow_double_equals: true, ..Default::default() };
+        let mut tokenizer = Tokenizer::with_options("a == b = c", options.clone());
+        assert_eq!(
+            tokenizer.tokenize().unwrap(),
+            vec![
+                Token::Identifier("a".to_string()),
+                Token::Equal,
+                Token::Identifier("b".to_string()),
+                Token::Equal,
+                Token::Identifier("c".to_string()),
+                Token::Eof,
+            ]
+        );
+        let mut parser = Parser::with_options(input, ParserOptions { tokenizer: options, ..ParserOptions::default() });
+        assert_eq!(
+            parser.parse().unwrap(),
+            Parser::new("SELECT * FROM t WHERE a = b;").parse().unwrap()
+        );
+    }
+
+    // Test BOOLEAN and INTEGER are accepted as aliases for BOOL and INT.
+    #[test]
+    fn test_sql_parser_type_keyword_aliases() {
+        let input = "CREATE TABLE t (a BOOLEAN, b INTEGER);";
+        let mut parser = Parser::new(input);
+        let statement = parser.parse().unwrap();
+        let column_list = create_table_columns(&statement);
+        let types: Vec<&DBType> = column_list.iter().map(|column| &column.column_type).collect();
+        assert_eq!(types, vec![&DBType::Bool, &DBType::Int]);
+        assert_eq!(statement.to_string(), "CREATE TABLE t (a BOOL, b INT)");
+    }
+
+    // Test deep nesting hits the configurable depth limit instead of overflowing the stack.
+    #[test]
+    fn test_pratt_parser_max_depth_limit() {
+        let input = format!("SELECT {}1{} FROM t;", "(".repeat(1000), ")".repeat(1000));
+        let mut parser = Parser::new(&input);
+        assert!(matches!(parser.parse(), Err(ParseError::DepthLimitExceeded)));
+
+        // A lower limit rejects nesting the default accepts.
+        let input = "SELECT ((((1)))) FROM t;";
+        assert!(Parser::new(input).parse().is_ok());
+        let options = ParserOptions { max_depth: 3, ..ParserOptions::default() };
+        let mut parser = Parser::with_options(input, options);
+        assert!(matches!(parser.parse(), Err(ParseError::DepthLimitExceeded)));
+    }
+
+    // Test statements end with ; by default or with a configured alternate delimiter.
+    #[test]
+    fn test_sql_parser_configurable_delimiter() {
+        let expected = Parser::new("SELECT a FROM t WHERE a = 1;").parse().unwrap();
+
+        let options = ParserOptions { delimiter: Token::Identifier("GO".to_string()), ..ParserOptions::default() };
+        let mut parser = Parser::with_options("SELECT a FROM t WHERE a = 1 GO", options.clone());
+        assert_eq!(parser.parse().unwrap(), expected);
+
+        // With another delimiter configured, ; no longer terminates.
+        let mut parser = Parser::with_options("SELECT a FROM t WHERE a = 1;", options.clone());
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::Semicolon))));
+
+        // The script helpers honor the configured delimiter too.
+        let script = "SELECT a FROM t WHERE a = 1 GO DELETE FROM t GO";
+        let (statement, offset) = parse_one_with_options(script, options.clone()).unwrap();
+        assert_eq!(statement, expected);
+        assert_eq!(&script[offset..], " DELETE FROM t GO");
+        assert_eq!(parse_script_with_options(script, options.clone()).unwrap().len(), 2);
+        let (table, columns) = parse_table_columns_with_options("CREATE TABLE t (id INT) GO", options.clone()).unwrap();
+        assert_eq!((table.as_str(), columns.len()), ("t", 1));
+        assert_eq!(
+            split_statements_with_options(script, options.tokenizer.clone(), &options.delimiter),
+            vec!["SELECT a FROM t WHERE a = 1 GO", "DELETE FROM t GO"]
+        );
+        // Splitting uses the same tokenizer options as parsing, e.g. a delimiter registered as a custom keyword.
+        let custom = ParserOptions {
+            tokenizer: TokenizerOptions { custom_keywords: vec!["go".to_string()], ..TokenizerOptions::default() },
+            delimiter: Token::Identifier("go".to_string()),
+            ..ParserOptions::default()
+        };
+        let script = "SELECT a FROM t go DELETE FROM t go";
+        assert_eq!(parse_script_with_options(script, custom.clone()).unwrap().len(), 2);
+        assert_eq!(
+            split_statements_with_options(script, custom.tokenizer.clone(), &custom.delimiter),
+            vec!["SELECT a FROM t go", "DELETE FROM t go"]
+        );
+        // With allow_trailing_comma, a trailing comma may come right before the configured delimiter.
+        let trailing = ParserOptions { allow_trailing_comma: true, ..options.clone() };
+        assert!(Parser::with_options("SELECT a FROM t ORDER BY a, GO", trailing.clone()).parse().is_ok());
+        assert!(Parser::with_options("SELECT a FROM t GROUP BY a, GO", trailing.clone()).parse().is_ok());
+        assert!(Parser::with_options("DELETE FROM t RETURNING a, GO", trailing).parse().is_ok());
+        // GROUP BY stops at the same later clauses as ORDER BY.
+        let trailing = ParserOptions { allow_trailing_comma: true, ..ParserOptions::default() };
+        assert!(Parser::with_options("SELECT a FROM t GROUP BY a, OFFSET 1;", trailing).parse().is_ok());
+
+        // An operator cannot be the delimiter, so division keeps working.
+        let options = ParserOptions { delimiter: Token::Divide, ..ParserOptions::default() };
+        let mut parser = Parser::with_options("SELECT a / 2 FROM t /", options);
+        assert!(matches!(parser.parse(), Err(ParseError::InvalidDelimiter(Token::Divide))));
+    }
+
+    // Test IN and NOT IN against a subquery alongside the value-list form.
+    #[test]
+    fn test_pratt_parser_in_subquery() {
+        let subquery = Statement::Select {
+            columns: vec![Expression::Identifier("user_id".to_string())],
+            into: None,
+            from: TableSource::Table("orders".to_string()),
+            r#where: None,
+            group_by: vec![],
+            having: None,
+            orderby: vec![],
+            limit: None,
+            offset: None,
+        };
+        assert_eq!(
+            parse_where("id IN (SELECT user_id FROM orders)"),
+            Expression::InSubquery {
+                expr: Box::new(Expression::Identifier("id".to_string())),
+                subquery: Box::new(subquery),
+                negated: false,
+            }
+        );
+        let expr = parse_where("id NOT IN (SELECT user_id FROM orders WHERE total > ?)");
+        assert!(matches!(&expr, Expression::InSubquery { negated: true, .. }));
+        assert_eq!(expr.to_string(), "(id NOT IN (SELECT user_id FROM orders WHERE (total > ?)))");
+        assert_eq!(expr.to_sql(), "id NOT IN (SELECT user_id FROM orders WHERE total > ?)");
+        assert!(matches!(parse_where("id IN (1, 2)"), Expression::InList { negated: false, .. }));
+    }
+
+    // Test Statement::format under different identifier quote styles and keyword case.
+    #[test]
+    fn test_sql_parser_format_quote_styles() {
+        let statement = Parser::new("SELECT name, COUNT(*) AS total FROM users WHERE age > 18 AND city = 'O''Hare' ORDER BY total DESC;")
+            .parse()
+            .unwrap();
+        let ansi = FormatOptions { identifier_quote: QuoteStyle::DoubleQuote, ..FormatOptions::default() };
+        assert_eq!(
+            statement.format(&ansi),
+            "SELECT \"name\", COUNT(*) AS \"total\" FROM \"users\" WHERE \"age\" > 18 AND \"city\" = 'O''Hare' ORDER BY \"total\" DESC"
+        );
+        let mysql = FormatOptions { identifier_quote: QuoteStyle::Backtick, uppercase_keywords: false, ..FormatOptions::default() };
+        assert_eq!(
+            statement.format(&mysql),
+            "select `name`, COUNT(*) as `total` from `users` where `age` > 18 and `city` = 'O''Hare' order by `total` desc"
+        );
+        assert_eq!(QuoteStyle::Bracket.quote("odd]name"), "[odd]]name]");
+        // Default options write bare identifiers and parenthesize only where needed.
+        let statement = Parser::new("UPDATE t SET a = (a + 1) * 2 WHERE b NOT IN (1, 2);").parse().unwrap();
+        assert_eq!(statement.format(&FormatOptions::default()), "UPDATE t SET a = (a + 1) * 2 WHERE b NOT IN (1, 2)");
+        // Collation names are double-quoted with embedded quotes doubled.
+        let expr = Expression::Collate {
+            expr: Box::new(Expression::Identifier("name".to_string())),
+            collation: "odd\"name".to_string(),
+        };
+        assert_eq!(expr.format(&FormatOptions::default()), "name COLLATE \"odd\"\"name\"");
+    }
+
+    // Test COMMENT '...' on a column definition, next to a column without one.
+    #[test]
+    fn test_sql_parser_create_table_column_comment() {
+        let input = "CREATE TABLE t (id INT PRIMARY KEY COMMENT 'the id', name VARCHAR(10));";
+        let statement = Parser::new(input).parse().unwrap();
+        let column_list = create_table_columns(&statement);
+        assert_eq!(column_list[0].comment, Some("the id".to_string()));
+        assert_eq!(column_list[0].constraints, vec![Constraint::PrimaryKey]);
+        assert_eq!(column_list[1].comment, None);
+        assert_eq!(
+            statement.to_string(),
+            "CREATE TABLE t (id INT PRIMARY KEY COMMENT 'the id', name VARCHAR(10))"
+        );
+        assert!(matches!(
+            Parser::new("CREATE TABLE t (id INT COMMENT 42);").parse(),
+            Err(ParseError::UnexpectedToken(Token::Number(42)))
+        ));
+    }
+
+    // Test debug_dump shows the tokens around the point where parsing failed.
+    #[test]
+    fn test_sql_parser_debug_dump() {
+        let mut parser = Parser::new("SELECT a FROM t WHERE a = ) ORDER BY a;");
+        assert!(matches!(parser.parse(), Err(ParseError::UnexpectedToken(Token::RightParentheses))));
+        let dump = parser.debug_dump();
+        assert_eq!(dump, "... FROM t WHERE a = >>> ) ORDER BY a ;");
+        // Only the last few consumed tokens are kept.
+        assert!(!dump.contains("SELECT"));
+    }
+
+    // Test output_column_count for explicit select lists, SELECT *, and non-queries.
+    #[test]
+    fn test_sql_parser_output_column_count() {
+        let count = |sql: &str| Parser::new(sql).parse().unwrap().output_column_count();
+        assert_eq!(count("SELECT id, COUNT(*), age + 1 AS next_age FROM users;"), Some(3));
+        assert_eq!(count("SELECT * FROM users;"), None);
+        assert_eq!(count("SELECT a, b FROM t UNION SELECT c, d FROM u;"), Some(2));
+        assert_eq!(count("DELETE FROM t;"), None);
+    }
+
+    // Test every binary operator from the operator table parses with its precedence and associativity.
+    #[test]
+    fn test_pratt_parser_operator_table() {
+        let operators = [
+            ("OR", Token::Keyword(Keyword::Or), BinaryOperator::Or, 10, Associativity::Left),
+            ("AND", Token::Keyword(Keyword::And), BinaryOperator::And, 20, Associativity::Left),
+            ("=", Token::Equal, BinaryOperator::Equal, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+            ("!=", Token::NotEqual, BinaryOperator::NotEqual, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+            (">", Token::GreaterThan, BinaryOperator::GreaterThan, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+            (">=", Token::GreaterThanOrEqual, BinaryOperator::GreaterThanOrEqual, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+            ("<", Token::LessThan, BinaryOperator::LessThan, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+            ("<=", Token::LessThanOrEqual, BinaryOperator::LessThanOrEqual, COMPARISON_PRECEDENCE, Associativity::NonAssociative),
+            ("+", Token::Plus, BinaryOperator::Plus, 50, Associativity::Left),
+            ("-", Token::Minus, BinaryOperator::Minus, 50, Associativity::Left),
+            ("*", Token::Star, BinaryOperator::Multiply, 60, Associativity::Left),
+            ("/", Token::Divide, BinaryOperator::Divide, 60, Associativity::Left),
+            ("^", Token::Caret, BinaryOperator::Power, 70, Associativity::Right),
+        ];
+        let parser = Parser::new("");
+        for (symbol, token, operator, precedence, associativity) in operators {
+            let expr = parse_where(&format!("a {} b", symbol));
+            assert_eq!(
+                expr,
+                Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("a".to_string())),
+                    operator: operator.clone(),
+                    right_operand: Box::new(Expression::Identifier("b".to_string())),
+                }
+            );
+            assert_eq!(parser.get_precedence(&token), precedence, "parser precedence of {}", symbol);
+            assert_eq!(operator.precedence(), precedence, "operator precedence of {}", symbol);
+            assert_eq!(operator.associativity(), associativity, "associativity of {}", symbol);
+            assert_eq!(expr.precedence(), precedence, "expression precedence of {}", symbol);
+        }
+        // Mixed precedences and left associativity are unchanged.
+        assert_eq!(parse_where("a - b - c * d ^ e > 1 OR f AND g").to_string(), "((((a - b) - (c * (d ^ e))) > 1) OR (f AND g))");
+        // ^ is right-associative: 2 ^ 3 ^ 2 is 2 ^ (3 ^ 2), and only a left-nested power needs parentheses.
+        let power = parse_where("2 ^ 3 ^ 2");
+        assert_eq!(power.to_string(), "(2 ^ (3 ^ 2))");
+        assert_eq!(power.to_sql(), "2 ^ 3 ^ 2");
+        assert_eq!(parse_where("(2 ^ 3) ^ 2").to_sql(), "(2 ^ 3) ^ 2");
+        // A non-associative operand of equal precedence keeps its parentheses on either side.
+        assert_eq!(parse_where("(a = b) = c").to_sql(), "(a = b) = c");
+        assert_eq!(parse_where("a = (b = c)").to_sql(), "a = (b = c)");
+    }
+
+    // Test IS [NOT] TRUE / FALSE / UNKNOWN and IS [NOT] NULL.
+    #[test]
+    fn test_pratt_parser_is_predicates() {
+        let active = Box::new(Expression::Identifier("active".to_string()));
+        assert_eq!(
+            parse_where("active IS TRUE"),
+            Expression::IsBool { expr: active.clone(), value: Some(true), negated: false }
+        );
+        assert_eq!(
+            parse_where("active IS NOT FALSE"),
+            Expression::IsBool { expr: active.clone(), value: Some(false), negated: true }
+        );
+        assert_eq!(
+            parse_where("active IS UNKNOWN"),
+            Expression::IsBool { expr: active.clone(), value: None, negated: false }
+        );
+        assert_eq!(parse_where("active IS NOT NULL"), Expression::IsNull { expr: active, negated: true });
+        // IS binds more loosely than comparisons and more tightly than AND.
+        let expr = parse_where("a = 1 IS NOT TRUE AND b IS NULL");
+        assert_eq!(expr.to_string(), "(((a = 1) IS NOT TRUE) AND (b IS NULL))");
+        assert_eq!(expr.to_sql(), "a = 1 IS NOT TRUE AND b IS NULL");
+        assert!(matches!(
+            Parser::new("SELECT * FROM t WHERE a IS 1;").parse(),
+            Err(ParseError::UnexpectedToken(Token::Number(1)))
+        ));
+    }
+
+    // Test parse_expression_only parses a bare expression and rejects trailing tokens.
+    #[test]
+    fn test_pratt_parser_parse_expression_only() {
+        let expr = Parser::new("1 + 2 * 3").parse_expression_only().unwrap();
+        assert_eq!(
+            expr,
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Number(1)),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Number(2)),
+                    operator: BinaryOperator::Multiply,
+                    right_operand: Box::new(Expression::Number(3)),
+                }),
+            }
+        );
+        let expr = Parser::new("NOT (a AND b);").parse_expression_only().unwrap();
+        assert_eq!(
+            expr,
+            Expression::UnaryOperation {
+                operator: UnaryOperator::Not,
+                operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("a".to_string())),
+                    operator: BinaryOperator::And,
+                    right_operand: Box::new(Expression::Identifier("b".to_string())),
+                }),
+            }
+        );
+        assert!(matches!(
+            Parser::new("1 + 2 3").parse_expression_only(),
+            Err(ParseError::UnexpectedToken(Token::Number(3)))
+        ));
+    }
+
+    // Test CURRENT_DATE / CURRENT_TIMESTAMP without parentheses, in a DEFAULT and a select list.
+    #[test]
+    fn test_sql_parser_current_date_and_timestamp() {
+        let statement = Parser::new("CREATE TABLE t (created TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL);").parse().unwrap();
+        let column_list = create_table_columns(&statement);
+        assert_eq!(
+            column_list[0].constraints,
+            vec![Constraint::Default(Expression::CurrentTimestamp), Constraint::NotNull]
+        );
+        assert_eq!(statement.to_string(), "CREATE TABLE t (created TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL)");
+        // FROM is still required, so the query names a table.
+        let statement = Parser::new("SELECT current_date FROM t;").parse().unwrap();
+        assert!(matches!(&statement, Statement::Select { columns, .. } if columns == &vec![Expression::CurrentDate]));
+        assert_eq!(statement.to_string(), "SELECT CURRENT_DATE FROM t");
+    }
+
+    // Test bracketed identifiers keep their exact text and report a missing ].
+    #[test]
+    fn test_tokenizer_bracketed_identifier() {
+        let tokens = Tokenizer::new("SELECT [order id] FROM [Select];").tokenize().unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::Identifier("order id".to_string()),
+                Token::Keyword(Keyword::From),
+                Token::Identifier("Select".to_string()),
+                Token::Semicolon,
+                Token::Eof,
+            ]
+        );
+        assert_eq!(Tokenizer::new("[a]]b]").tokenize().unwrap()[0], Token::Identifier("a]b".to_string()));
+        assert_eq!(Tokenizer::new("SELECT [order id FROM t;").tokenize(), Err(TokenizerError::UnterminatedIdentifier));
+    }
+
+    // Test max_identifier_length rejects names longer than the limit.
+    #[test]
+    fn test_sql_parser_max_identifier_length() {
+        let options = ParserOptions { max_identifier_length: Some(10), ..ParserOptions::default() };
+        let parse = |sql: &str| Parser::with_options(sql, options.clone()).parse();
+        assert!(parse("CREATE TABLE short_name (id INT);").is_ok());
+        assert!(parse("CREATE TABLE exactly_10 (id INT);").is_ok());
+        match parse("CREATE TABLE eleven_char (id INT);") {
+            Err(ParseError::IdentifierTooLong(name)) => assert_eq!(name, "eleven_char"),
+            other => panic!("expected IdentifierTooLong, got {:?}", other),
+        }
+        // Column references in expressions are checked too, plain or qualified.
+        match parse("SELECT eleven_char FROM t;") {
+            Err(ParseError::IdentifierTooLong(name)) => assert_eq!(name, "eleven_char"),
+            other => panic!("expected IdentifierTooLong, got {:?}", other),
+        }
+        assert!(matches!(parse("SELECT id FROM t WHERE t.eleven_char = 1;"), Err(ParseError::IdentifierTooLong(_))));
+        assert!(matches!(parse("SELECT * FROM t WHERE eleven_char > 1;"), Err(ParseError::IdentifierTooLong(_))));
+        // Unlimited by default.
+        assert!(Parser::new("CREATE TABLE eleven_char (id INT);").parse().is_ok());
+    }
+
+    // Test REFERENCES with ON DELETE / ON UPDATE referential actions.
+    #[test]
+    fn test_sql_parser_references_actions() {
+        let input = "CREATE TABLE orders (user_id INT REFERENCES users(id) ON DELETE CASCADE ON UPDATE SET NULL, item INT REFERENCES items);";
+        let statement = Parser::new(input).parse().unwrap();
+        let column_list = create_table_columns(&statement);
+        assert_eq!(
+            column_list[0].constraints,
+            vec![Constraint::References {
+                table: "users".to_string(),
+                column: Some("id".to_string()),
+                referential_actions: ReferentialActions {
+                    on_delete: Some(ReferentialAction::Cascade),
+                    on_update: Some(ReferentialAction::SetNull),
+                },
+            }]
+        );
+        assert_eq!(
+            column_list[1].constraints,
+            vec![Constraint::References {
+                table: "items".to_string(),
+                column: None,
+                referential_actions: ReferentialActions::default(),
+            }]
+        );
+        assert_eq!(
+            statement.to_string(),
+            "CREATE TABLE orders (user_id INT REFERENCES users(id) ON DELETE CASCADE ON UPDATE SET NULL, item INT REFERENCES items)"
+        );
+        // Each action may be given only once.
+        assert!(matches!(
+            Parser::new("CREATE TABLE t (a INT REFERENCES u ON DELETE CASCADE ON DELETE RESTRICT);").parse(),
+            Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Delete)))
+        ));
+    }
+
+    // Test walk_statement reaches CTE bodies and subqueries inside expressions.
+    #[test]
+    fn test_sql_parser_walk_statement_counts_selects() {
+        #[derive(Default)]
+        struct Counter {
+            selects: usize,
+            params: usize,
+        }
+        impl StatementVisitor for Counter {
+            fn visit_statement(&mut self, statement: &Statement) {
+                if matches!(statement, Statement::Select { .. } | Statement::SelectAll { .. }) {
+                    self.selects += 1;
+                }
+            }
+            fn visit_expression(&mut self, expr: &Expression) {
+                if matches!(expr, Expression::Parameter(_)) {
+                    self.params += 1;
+                }
+            }
+        }
+        let input = "WITH recent AS (SELECT * FROM orders WHERE total > ?) \
+                     SELECT user_id FROM recent WHERE user_id IN (SELECT id FROM users WHERE name = ?);";
+        let statement = Parser::new(input).parse().unwrap();
+        let mut counter = Counter::default();
+        walk_statement(&statement, &mut counter);
+        assert_eq!(counter.selects, 3);
+        assert_eq!(counter.params, 2);
+    }
+
+    // Test GROUP BY ROLLUP, CUBE, and GROUPING SETS.
+    #[test]
+    fn test_sql_parser_group_by_rollup_cube() {
+        let group_by = |sql: &str| match Parser::new(sql).parse().unwrap() {
+            Statement::Select { group_by, .. } => group_by,
+            other => panic!("expected SELECT, got {:?}", other),
+        };
+        let a = || Expression::Identifier("a".to_string());
+        let b = || Expression::Identifier("b".to_string());
+        assert_eq!(
+            group_by("SELECT a, b, COUNT(*) FROM t GROUP BY ROLLUP (a, b);"),
+            vec![GroupByKind::Rollup(vec![a(), b()])]
+        );
+        assert_eq!(
+            group_by("SELECT a, b FROM t GROUP BY a, CUBE (b);"),
+            vec![GroupByKind::Expression(a()), GroupByKind::Cube(vec![b()])]
+        );
+        assert_eq!(
+            group_by("SELECT a FROM t GROUP BY GROUPING SETS ((a, b), b, ());"),
+            vec![GroupByKind::GroupingSets(vec![vec![a(), b()], vec![b()], vec![]])]
+        );
+        let statement = Parser::new("SELECT a FROM t GROUP BY CUBE (a, b) HAVING COUNT(*) > 1;").parse().unwrap();
+        assert_eq!(statement.to_string(), "SELECT a FROM t GROUP BY CUBE (a, b) HAVING (COUNT(*) > 1)");
+        let statement = Parser::new("SELECT a FROM t GROUP BY GROUPING SETS ((a), ());").parse().unwrap();
+        assert_eq!(statement.to_string(), "SELECT a FROM t GROUP BY GROUPING SETS ((a), ())");
+    }
+
+    // Test semantically_eq ignores operand order and grouping for commutative operators only.
+    #[test]
+    fn test_pratt_parser_semantically_eq() {
+        let same = |a: &str, b: &str| parse_where(a).semantically_eq(&parse_where(b));
+        assert!(same("a + b = 1", "1 = b + a"));
+        assert!(same("(a AND b) AND c", "a AND (c AND b)"));
+        assert!(same("x * (y + 2) != 3 OR flag", "flag OR 3 != (2 + y) * x"));
+        assert!(!same("a - b = 1", "b - a = 1"));
+        assert!(!same("a > b", "b > a"));
+        assert!(!same("a + b * c", "(a + b) * c"));
+        // Derived equality stays structural.
+        assert_ne!(parse_where("a + b"), parse_where("b + a"));
+    }
+
+    // Test NOT wraps a whole parenthesized predicate and binds more loosely than comparisons.
+    #[test]
+    fn test_pratt_parser_not_precedence() {
+        let ident = |name: &str| Box::new(Expression::Identifier(name.to_string()));
+        let equals = |name: &str, n: u64| Expression::BinaryOperation {
+            left_operand: ident(name),
+            operator: BinaryOperator::Equal,
+            right_operand: Box::new(Expression::Number(n)),
+        };
+        assert_eq!(
+            parse_where("NOT (a = 1 AND b = 2)"),
+            Expression::UnaryOperation {
+                operator: UnaryOperator::Not,
+                operand: Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(equals("a", 1)),
+                    operator: BinaryOperator::And,
+                    right_operand: Box::new(equals("b", 2)),
+                }),
+            }
+        );
+        assert_eq!(
+            parse_where("NOT a = 1"),
+            Expression::UnaryOperation { operator: UnaryOperator::Not, operand: Box::new(equals("a", 1)) }
+        );
+        // AND still binds more loosely than NOT.
+        assert_eq!(
+            parse_where("NOT a = 1 AND b = 2"),
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::UnaryOperation {
+                    operator: UnaryOperator::Not,
+                    operand: Box::new(equals("a", 1)),
+                }),
+                operator: BinaryOperator::And,
+                right_operand: Box::new(equals("b", 2)),
+            }
+        );
+        assert_eq!(parse_where("NOT a = 1").to_sql(), "NOT a = 1");
+        assert_eq!(parse_where("NOT (a OR b)").to_sql(), "NOT (a OR b)");
+    }
+
+    // Test the serde feature: a parsed statement serializes to JSON naming its kind and round-trips.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_sql_parser_serde_json() {
+        let statement = Parser::new("SELECT name FROM users WHERE age > ?;").parse().unwrap();
+        let json = serde_json::to_string(&vec![statement.clone()]).unwrap();
+        assert!(json.starts_with("[{\"Select\":"), "{}", json);
+        assert!(json.contains("\"from\":{\"Table\":\"users\"}"), "{}", json);
+        let back: Vec<Statement> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, vec![statement]);
+    }
+
+    // Test a window function with PARTITION BY and ORDER BY after OVER.
+    #[test]
+    fn test_pratt_parser_window_function() {
+        let statement = Parser::new("SELECT ROW_NUMBER() OVER (PARTITION BY dept ORDER BY salary DESC) AS rn FROM emp;")
+            .parse()
+            .unwrap();
+        let Statement::Select { columns, .. } = &statement else {
+            panic!("expected SELECT, got {:?}", statement);
+        };
+        assert_eq!(
+            columns[0],
+            Expression::Alias {
+                expr: Box::new(Expression::WindowFunction {
+                    func: Box::new(Expression::FunctionCall {
+                        name: "ROW_NUMBER".to_string(),
+                        distinct: false,
+                        args: vec![],
+                        filter: None,
+                    }),
+                    partition_by: vec![Expression::Identifier("dept".to_string())],
+                    order_by: vec![OrderByItem {
+                        expr: Expression::Identifier("salary".to_string()),
+                        order: Some(SortOrder::Desc),
+                    }],
+                }),
+                alias: "rn".to_string(),
+            }
+        );
+        assert_eq!(
+            statement.to_string(),
+            "SELECT ROW_NUMBER() OVER (PARTITION BY dept ORDER BY salary DESC) AS rn FROM emp"
+        );
+        // Either part of the window spec may be omitted.
+        let expr = Parser::new("SUM(x) OVER (ORDER BY day)").parse_expression_only().unwrap();
+        assert_eq!(expr.to_sql(), "SUM(x) OVER (ORDER BY day)");
+        assert_eq!(Parser::new("COUNT(*) OVER ()").parse_expression_only().unwrap().to_string(), "COUNT(*) OVER ()");
+    }
+
+    // Test control characters other than whitespace report their code point.
+    #[test]
+    fn test_tokenizer_control_character() {
+        let result = Tokenizer::new("SELECT a\0 FROM t;").tokenize();
+        assert_eq!(result, Err(TokenizerError::ControlCharacter(0)));
+        let result = Tokenizer::new("SELECT a\u{c}FROM t;").tokenize();
+        assert_eq!(result, Err(TokenizerError::ControlCharacter(0x0C)));
+        assert_eq!(TokenizerError::ControlCharacter(0x0C).to_string(), "Unexpected control character U+000C");
+        // Tabs and newlines are still plain whitespace.
+        assert!(Tokenizer::new("SELECT a\n\tFROM t;").tokenize().is_ok());
+    }
+
+    // Test EXPLAIN [ANALYZE] wraps any statement and DESCRIBE takes a table name.
+    #[test]
+    fn test_sql_parser_explain_and_describe() {
+        let statement = Parser::new("EXPLAIN SELECT * FROM t;").parse().unwrap();
+        let Statement::Explain { analyze, inner } = &statement else {
+            panic!("expected EXPLAIN, got {:?}", statement);
+        };
+        assert!(!analyze);
+        assert!(matches!(**inner, Statement::SelectAll { .. }));
+        assert_eq!(statement.to_string(), "EXPLAIN SELECT * FROM t");
+        assert!(statement.is_read_only());
+
+        let statement = Parser::new("EXPLAIN ANALYZE DELETE FROM t WHERE id = 1;").parse().unwrap();
+        assert_eq!(statement.to_string(), "EXPLAIN ANALYZE DELETE FROM t WHERE (id = 1)");
+        // EXPLAIN ANALYZE executes the statement, so a DELETE is not read-only.
+        assert!(!statement.is_read_only());
+
+        let statement = Parser::new("DESCRIBE users;").parse().unwrap();
+        assert_eq!(statement, Statement::Describe { table: "users".to_string() });
+        assert_eq!(statement.to_string(), "DESCRIBE users");
+        assert!(Parser::new("DESCRIBE;").parse().is_err());
+
+        // Nested EXPLAINs count toward max_depth instead of recursing without bound.
+        let options = ParserOptions { max_depth: 3, ..ParserOptions::default() };
+        assert!(Parser::with_options("EXPLAIN EXPLAIN SELECT * FROM t;", options.clone()).parse().is_ok());
+        let deep = format!("{}SELECT * FROM t;", "EXPLAIN ".repeat(4));
+        assert!(matches!(Parser::with_options(&deep, options).parse(), Err(ParseError::DepthLimitExceeded)));
+        let very_deep = format!("{}SELECT * FROM t;", "EXPLAIN ".repeat(100_000));
+        assert!(matches!(Parser::new(&very_deep).parse(), Err(ParseError::DepthLimitExceeded)));
+    }
+
+    // Test bind_params substitutes ? and $n parameters and checks the value count.
+    #[test]
+    fn test_sql_parser_bind_params() {
+        let statement = Parser::new("SELECT * FROM t WHERE a = ? AND b = ?;").parse().unwrap();
+        let bound = statement
+            .bind_params(&[Expression::Number(1), Expression::String("x".to_string())])
+            .unwrap();
+        assert_eq!(bound.to_string(), "SELECT * FROM t WHERE ((a = 1) AND (b = \"x\"))");
+        assert!(bound.placeholders().is_empty());
+        // $n refers to values by position and may repeat; subqueries are bound too.
+        let statement = Parser::new("SELECT * FROM t WHERE a = $2 OR a IN (SELECT id FROM u WHERE x = $1);").parse().unwrap();
+        let bound = statement.bind_params(&[Expression::Number(5), Expression::Number(6)]).unwrap();
+        assert_eq!(bound.to_string(), "SELECT * FROM t WHERE ((a = 6) OR (a IN (SELECT id FROM u WHERE (x = 5))))");
+
+        let statement = Parser::new("SELECT * FROM t WHERE a = ? AND b = ?;").parse().unwrap();
+        assert_eq!(
+            statement.bind_params(&[Expression::Number(1)]),
+            Err(BindError::CountMismatch { expected: 2, supplied: 1 })
+        );
+        assert_eq!(
+            BindError::CountMismatch { expected: 2, supplied: 1 }.to_string(),
+            "Expected 2 bind values, got 1"
+        );
+    }
+
+    // Test GENERATED ALWAYS AS (expr) captures the expression and the STORED flag.
+    #[test]
+    fn test_sql_parser_generated_column() {
+        let input = "CREATE TABLE items (total INT GENERATED ALWAYS AS (qty * price) STORED NOT NULL, half INT GENERATED ALWAYS AS (total / 2) VIRTUAL);";
+        let statement = Parser::new(input).parse().unwrap();
+        let column_list = create_table_columns(&statement);
+        assert_eq!(
+            column_list[0].constraints,
+            vec![
+                Constraint::Generated {
+                    expr: Expression::BinaryOperation {
+                        left_operand: Box::new(Expression::Identifier("qty".to_string())),
+                        operator: BinaryOperator::Multiply,
+                        right_operand: Box::new(Expression::Identifier("price".to_string())),
+                    },
+                    stored: true,
+                },
+                Constraint::NotNull,
+            ]
+        );
+        assert!(matches!(column_list[1].constraints[0], Constraint::Generated { stored: false, .. }));
+        assert_eq!(
+            statement.to_string(),
+            "CREATE TABLE items (total INT GENERATED ALWAYS AS ((qty * price)) STORED NOT NULL, half INT GENERATED ALWAYS AS ((total / 2)))"
+        );
+        assert!(Parser::new("CREATE TABLE t (a INT GENERATED AS (1));").parse().is_err());
+    }
+
+    // Test split_statements ignores semicolons inside strings and comments.
+    #[test]
+    fn test_tokenizer_split_statements() {
+        let script = "SELECT 'a;b' FROM t;\n-- ; not a split\nSELECT x FROM u /* ; */ WHERE y = 1;;\nSELECT z FROM v";
+        assert_eq!(
+            split_statements(script),
+            vec![
+                "SELECT 'a;b' FROM t;".to_string(),
+                "SELECT x FROM u /* ; */ WHERE y = 1;".to_string(),
+                "SELECT z FROM v".to_string(),
+            ]
+        );
+        assert!(split_statements("  -- only a comment\n").is_empty());
+        // A script that does not tokenize is kept whole.
+        assert_eq!(split_statements("SELECT 'open; FROM t;"), vec!["SELECT 'open; FROM t;".to_string()]);
+    }
+
+    // Test ORDER BY items may be function calls and arithmetic, with the direction kept apart.
+    #[test]
+    fn test_sql_parser_order_by_expressions() {
+        let statement = Parser::new("SELECT name FROM users ORDER BY LENGTH(name) DESC, created + 1;").parse().unwrap();
+        let Statement::Select { orderby, .. } = &statement else {
+            panic!("expected SELECT, got {:?}", statement);
+        };
+        assert_eq!(
+            orderby,
+            &vec![
+                OrderByItem {
+                    expr: Expression::FunctionCall {
+                        name: "LENGTH".to_string(),
+                        distinct: false,
+                        args: vec![Expression::Identifier("name".to_string())],
+                        filter: None,
+                    },
+                    order: Some(SortOrder::Desc),
+                },
+                OrderByItem {
+                    expr: Expression::BinaryOperation {
+                        left_operand: Box::new(Expression::Identifier("created".to_string())),
+                        operator: BinaryOperator::Plus,
+                        right_operand: Box::new(Expression::Number(1)),
+                    },
+                    order: None,
+                },
+            ]
+        );
+        let statement = Parser::new("SELECT * FROM t ORDER BY a * 2 ASC;").parse().unwrap();
+        let Statement::SelectAll { orderby, .. } = &statement else {
+            panic!("expected SELECT *, got {:?}", statement);
+        };
+        assert_eq!(orderby[0].expr.to_sql(), "a * 2");
+        assert_eq!(orderby[0].order, Some(SortOrder::Asc));
+    }
+
+    // Test ParseError messages show token text, and end of input in words.
+    #[test]
+    fn test_sql_parser_error_messages() {
+        let error = Parser::new("SELECT name FROM").parse().unwrap_err();
+        assert_eq!(error.to_string(), "Unexpected end of input");
+        let error = Parser::new("SELECT FROM users;").parse().unwrap_err();
+        assert_eq!(error.to_string(), "Unexpected token: FROM");
+        assert_eq!(ParseError::UnexpectedToken(Token::Invalid('#')).to_string(), "Unexpected character: #");
+        assert_eq!(ParseError::UnexpectedToken(Token::Comma).to_string(), "Unexpected token: ,");
+    }
+
+    // Test COALESCE and NULLIF argument counts are checked, other functions are not.
+    #[test]
+    fn test_pratt_parser_coalesce_nullif_arity() {
+        let expr = Parser::new("COALESCE(a, b, 0)").parse_expression_only().unwrap();
+        assert_eq!(
+            expr,
+            Expression::FunctionCall {
+                name: "COALESCE".to_string(),
+                distinct: false,
+                args: vec![
+                    Expression::Identifier("a".to_string()),
+                    Expression::Identifier("b".to_string()),
+                    Expression::Number(0),
+                ],
+                filter: None,
+            }
+        );
+        assert!(Parser::new("nullif(a, b)").parse_expression_only().is_ok());
+        let error = Parser::new("SELECT NULLIF(a) FROM t;").parse().unwrap_err();
+        assert!(matches!(
+            &error,
+            ParseError::WrongArgumentCount { function, expected, found: 1 } if function == "NULLIF" && expected == "2"
+        ));
+        assert_eq!(error.to_string(), "NULLIF expects 2 argument(s), got 1");
+        assert!(matches!(
+            Parser::new("COALESCE()").parse_expression_only(),
+            Err(ParseError::WrongArgumentCount { found: 0, .. })
+        ));
+        assert!(Parser::new("MY_FUNC()").parse_expression_only().is_ok());
+    }
+
+    // Test case_sensitive_keywords only recognizes the uppercase spelling of keywords.
+    #[test]
+    fn test_tokenizer_case_sensitive_keywords() {
+        let tokens = Tokenizer::new("SELECT Select").tokenize().unwrap();
+        assert_eq!(tokens[..2], [Token::Keyword(Keyword::Select), Token::Keyword(Keyword::Select)]);
+
+        let options = TokenizerOptions { case_sensitive_keywords: true, ..Default::default() };
+        let tokens = Tokenizer::with_options("SELECT Select select", options).tokenize().unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::Keyword(Keyword::Select),
+                Token::Identifier("Select".to_string()),
+                Token::Identifier("select".to_string()),
+                Token::Eof,
+            ]
+        );
+    }
+
+    // Test a bare boolean column is accepted as the WHERE predicate.
+    #[test]
+    fn test_sql_parser_where_bare_boolean_column() {
+        let statement = Parser::new("SELECT * FROM users WHERE active ORDER BY id;").parse().unwrap();
+        let Statement::SelectAll { r#where, orderby, .. } = &statement else {
+            panic!("expected SELECT *, got {:?}", statement);
+        };
+        assert_eq!(r#where, &Some(Expression::Identifier("active".to_string())));
+        assert_eq!(orderby[0].expr, Expression::Identifier("id".to_string()));
+
+        let statement = Parser::new("SELECT * FROM users WHERE active = true;").parse().unwrap();
+        let Statement::SelectAll { r#where, .. } = &statement else {
+            panic!("expected SELECT *, got {:?}", statement);
+        };
+        assert_eq!(
+            r#where,
+            &Some(Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("active".to_string())),
+                operator: BinaryOperator::Equal,
+                right_operand: Box::new(Expression::Bool(true)),
+            })
+        );
+        assert!(Parser::new("DELETE FROM users WHERE NOT active;").parse().is_ok());
+    }
+
+    // Test parse_one returns the offset just past the first statement's semicolon.
+    #[test]
+    fn test_sql_parser_parse_one_offset() {
+        let input = "SELECT * FROM a; SELECT * FROM b;";
+        let (statement, offset) = parse_one(input).unwrap();
+        assert_eq!(statement.to_string(), "SELECT * FROM a");
+        assert_eq!(offset, 16);
+        assert_eq!(&input[offset..], " SELECT * FROM b;");
+        let (statement, rest) = parse_one(&input[offset..]).unwrap();
+        assert_eq!(statement.to_string(), "SELECT * FROM b");
+        assert_eq!(offset + rest, input.len());
+        assert!(matches!(parse_one("SELECT 'x FROM a;"), Err(ParseError::Tokenizer(_))));
+    }
+
+    // Test DEFAULT may stand in for a value in an INSERT VALUES tuple.
+    #[test]
+    fn test_sql_parser_insert_default_value() {
+        let statement = Parser::new("INSERT INTO t (a, b) VALUES (DEFAULT, 5);").parse().unwrap();
+        assert_eq!(
+            statement,
+            Statement::Insert {
+                table_name: "t".to_string(),
+                columns: vec!["a".to_string(), "b".to_string()],
+                source: InsertSource::Values(vec![vec![Expression::Default, Expression::Number(5)]]),
+                on_conflict: None,
+                returning: vec![],
+            }
+        );
+        assert_eq!(statement.to_string(), "INSERT INTO t (a, b) VALUES (DEFAULT, 5)");
+        // DEFAULT VALUES is still the whole-row form.
+        assert!(matches!(
+            Parser::new("INSERT INTO t DEFAULT VALUES;").parse(),
+            Ok(Statement::Insert { source: InsertSource::DefaultValues, .. })
+        ));
+    }
+
+    // Test ASC/DESC are only accepted in ORDER BY items, not in the select list.
+    #[test]
+    fn test_sql_parser_asc_outside_order_by() {
+        assert!(matches!(
+            Parser::new("SELECT a ASC FROM t;").parse(),
+            Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Asc)))
+        ));
+        assert!(matches!(
+            Parser::new("SELECT * FROM t WHERE a DESC;").parse(),
+            Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Desc)))
+        ));
+        assert!(Parser::new("SELECT a FROM t ORDER BY a ASC;").parse().is_ok());
+    }
+
+    // Test fold_unary_minus turns DEFAULT -1 into a negative literal and later constraints still parse.
+    #[test]
+    fn test_pratt_parser_fold_unary_minus() {
+        let input = "CREATE TABLE t (a INT DEFAULT -1 NOT NULL, b INT CHECK (b > -5));";
+        let statement = Parser::new(input).parse().unwrap();
+        let column_list = create_table_columns(&statement);
+        assert_eq!(
+            column_list[0].constraints,
+            vec![
+                Constraint::Default(Expression::UnaryOperation {
+                    operator: UnaryOperator::Minus,
+                    operand: Box::new(Expression::Number(1)),
+                }),
+                Constraint::NotNull,
+            ]
+        );
+
+        let options = ParserOptions { fold_unary_minus: true, ..ParserOptions::default() };
+        let statement = Parser::with_options(input, options.clone()).parse().unwrap();
+        let column_list = create_table_columns(&statement);
+        assert_eq!(column_list[0].constraints, vec![Constraint::Default(Expression::SignedNumber(-1)), Constraint::NotNull]);
+        assert_eq!(
+            column_list[1].constraints,
+            vec![Constraint::Check(Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("b".to_string())),
+                operator: BinaryOperator::GreaterThan,
+                right_operand: Box::new(Expression::SignedNumber(-5)),
+            })]
+        );
+        // The most negative i64 folds too, although its magnitude does not fit in an i64; one past it does not.
+        let expr = Parser::with_options("-9223372036854775808", options.clone()).parse_expression_only().unwrap();
+        assert_eq!(expr, Expression::SignedNumber(i64::MIN));
+        assert_eq!(expr.to_sql(), "-9223372036854775808");
+        let expr = Parser::with_options("-9223372036854775809", options.clone()).parse_expression_only().unwrap();
+        assert!(matches!(expr, Expression::UnaryOperation { operator: UnaryOperator::Minus, .. }));
+        // Only a bare literal folds: the minus still applies to the whole power.
+        let expr = Parser::with_options("-2 ^ 2", options).parse_expression_only().unwrap();
+        assert!(matches!(expr, Expression::UnaryOperation { operator: UnaryOperator::Minus, .. }));
+        assert_eq!(
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::SignedNumber(-2)),
+                operator: BinaryOperator::Power,
+                right_operand: Box::new(Expression::Number(2)),
+            }
+            .to_sql(),
+            "(-2) ^ 2"
+        );
+    }
+
+    // Test a table-valued function in FROM with arguments and an alias.
+    #[test]
+    fn test_sql_parser_from_table_function() {
+        let statement = Parser::new("SELECT * FROM generate_series(1, 10) AS s;").parse().unwrap();
+        let Statement::SelectAll { from, .. } = &statement else {
+            panic!("expected SELECT *, got {:?}", statement);
+        };
+        assert_eq!(
+            from,
+            &TableSource::Function {
+                name: "generate_series".to_string(),
+                args: vec![Expression::Number(1), Expression::Number(10)],
+                alias: Some("s".to_string()),
+            }
+        );
+        assert_eq!(statement.to_string(), "SELECT * FROM generate_series(1, 10) AS s");
+        // The alias may omit AS, and function arguments take bind parameters.
+        let statement = Parser::new("SELECT n FROM generate_series(1, ?) s WHERE n > 2;").parse().unwrap();
+        assert_eq!(statement.to_string(), "SELECT n FROM generate_series(1, ?) AS s WHERE (n > 2)");
+        assert_eq!(statement.placeholders(), vec![ParamKind::Anonymous]);
+        assert_eq!(Parser::new("SELECT * FROM now();").parse().unwrap().to_string(), "SELECT * FROM now()");
+    }
+
+    // Test empty parentheses in expression position report EmptyParentheses.
+    #[test]
+    fn test_pratt_parser_empty_parentheses() {
+        assert!(matches!(Parser::new("SELECT ();").parse(), Err(ParseError::EmptyParentheses)));
+        assert!(matches!(Parser::new("SELECT a FROM t WHERE a = ();").parse(), Err(ParseError::EmptyParentheses)));
+        assert_eq!(ParseError::EmptyParentheses.to_string(), "Empty parentheses where an expression is expected");
+        // Empty argument lists are still fine.
+        assert!(Parser::new("SELECT NOW() FROM t;").parse().is_ok());
+    }
+
+    // Test a dotted name is a qualified column, or a qualified function call when ( follows.
+    #[test]
+    fn test_pratt_parser_qualified_names() {
+        let statement = Parser::new("SELECT math.sqrt(x), t.a FROM t;").parse().unwrap();
+        let Statement::Select { columns, .. } = &statement else {
+            panic!("expected SELECT, got {:?}", statement);
+        };
+        assert_eq!(
+            columns,
+            &vec![
+                Expression::FunctionCall {
+                    name: "math.sqrt".to_string(),
+                    distinct: false,
+                    args: vec![Expression::Identifier("x".to_string())],
+                    filter: None,
+                },
+                Expression::CompoundIdentifier(vec!["t".to_string(), "a".to_string()]),
+            ]
+        );
+        assert_eq!(statement.to_string(), "SELECT math.sqrt(x), t.a FROM t");
+        let options = FormatOptions { identifier_quote: QuoteStyle::DoubleQuote, ..FormatOptions::default() };
+        assert_eq!(statement.format(&options), "SELECT math.sqrt(\"x\"), \"t\".\"a\" FROM \"t\"");
+        assert!(matches!(
+            Parser::new("SELECT a. FROM t;").parse(),
+            Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::From)))
+        ));
+    }
+
+    // Test words listed in custom_keywords tokenize as CustomKeyword instead of Identifier, and parse as names.
+    #[test]
+    fn test_tokenizer_custom_keywords() {
+        let options = TokenizerOptions { custom_keywords: vec!["upsert".to_string()], ..Default::default() };
+        let tokens = Tokenizer::with_options("upsert UPSERT upserts", options).tokenize().unwrap();
+        assert_eq!(
+            tokens,
+            vec![
+                Token::CustomKeyword("upsert".to_string()),
+                Token::CustomKeyword("upsert".to_string()),
+                Token::Identifier("upserts".to_string()),
+                Token::Eof,
+            ]
+        );
+        // Without the option the word is an ordinary identifier.
+        let tokens = Tokenizer::new("upsert").tokenize().unwrap();
+        assert_eq!(tokens[0], Token::Identifier("upsert".to_string()));
+        // The parser reads custom keywords as names, in name and expression positions alike.
+        let options = ParserOptions {
+            tokenizer: TokenizerOptions { custom_keywords: vec!["Upsert".to_string()], ..Default::default() },
+            ..ParserOptions::default()
+        };
+        let statement = Parser::with_options("SELECT upsert FROM UPSERT WHERE upsert > 1;", options).parse().unwrap();
+        assert_eq!(statement, Parser::new("SELECT Upsert FROM Upsert WHERE Upsert > 1;").parse().unwrap());
+    }
+
+    // Test parse_script returns each statement with its own span in the input.
+    #[test]
+    fn test_sql_parser_parse_script_spans() {
+        let input = "SELECT * FROM a;\n  -- second\n  DELETE FROM b WHERE id = 1;";
+        let statements = parse_script(input).unwrap();
+        assert_eq!(statements.len(), 2);
+        assert_eq!(&input[statements[0].span.start..statements[0].span.end], "SELECT * FROM a;");
+        assert_eq!(&input[statements[1].span.start..statements[1].span.end], "DELETE FROM b WHERE id = 1;");
+        assert!(statements[0].span.end <= statements[1].span.start);
+        assert!(matches!(statements[1].node, Statement::Delete { .. }));
+        // The last statement may end at the end of input.
+        let statements = parse_script("SELECT * FROM a; SELECT * FROM b").unwrap();
+        assert_eq!(statements[1].span, Span { start: 17, end: 32 });
+        assert!(parse_script("").unwrap().is_empty());
+    }
+
+    // Test the :: cast shorthand binds tighter than arithmetic.
+    #[test]
+    fn test_pratt_parser_double_colon_cast() {
+        let expr = Parser::new("age::INT").parse_expression_only().unwrap();
+        assert_eq!(
+            expr,
+            Expression::Cast { expr: Box::new(Expression::Identifier("age".to_string())), data_type: DBType::Int }
+        );
+        // Only b is cast.
+        let expr = Parser::new("a + b::VARCHAR(10)").parse_expression_only().unwrap();
+        assert_eq!(
+            expr,
+            Expression::BinaryOperation {
+                left_operand: Box::new(Expression::Identifier("a".to_string())),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::Cast {
+                    expr: Box::new(Expression::Identifier("b".to_string())),
+                    data_type: DBType::Varchar(10),
+                }),
+            }
+        );
+        assert_eq!(Parser::new("(a + b)::INT").parse_expression_only().unwrap().to_sql(), "(a + b)::INT");
+        let tokens = Tokenizer::new("a::INT :x").tokenize_with_spans().unwrap();
+        assert_eq!(tokens[1], (Token::DoubleColon, Span { start: 1, end: 3 }));
+        assert_eq!(tokens[3].0, Token::NamedParam(":x".to_string()));
+        assert_eq!(Tokenizer::new("a : b").tokenize(), Err(TokenizerError::UnexpectedChar(':')));
+    }
+
+    // Test SELECT ... INTO records the new table between the select list and FROM.
+    #[test]
+    fn test_sql_parser_select_into() {
+        let statement = Parser::new("SELECT a, b INTO new_table FROM src WHERE a > 1;").parse().unwrap();
+        let Statement::Select { columns, into, from, .. } = &statement else {
+            panic!("expected SELECT, got {:?}", statement);
+        };
+        assert_eq!(columns.len(), 2);
+        assert_eq!(into, &Some("new_table".to_string()));
+        assert_eq!(from, &TableSource::Table("src".to_string()));
+        assert_eq!(statement.to_string(), "SELECT a, b INTO new_table FROM src WHERE (a > 1)");
+        // Creating a table is not read-only.
+        assert!(!statement.is_read_only());
+
+        let statement = Parser::new("SELECT * INTO backup FROM users;").parse().unwrap();
+        assert!(matches!(&statement, Statement::SelectAll { into: Some(table), .. } if table == "backup"));
+        assert_eq!(statement.format(&FormatOptions::default()), "SELECT * INTO backup FROM users");
+    }
+
+    // Test normalized_sql gives the same text for inputs differing only in letter case.
+    #[test]
+    fn test_sql_parser_normalized_sql() {
+        let a = Parser::new("select Name, count(ID) from Users where Age > 18 order by Name;").parse().unwrap();
+        let b = Parser::new("SELECT name, COUNT(id) FROM USERS WHERE age > 18 ORDER BY NAME;").parse().unwrap();
+        assert_eq!(a.normalized_sql(), "SELECT name, count(id) FROM users WHERE age > 18 ORDER BY name");
+        assert_eq!(a.normalized_sql(), b.normalized_sql());
+        assert_eq!(Parser::new(&format!("{};", a.normalized_sql())).parse().unwrap().normalized_sql(), a.normalized_sql());
+        // Names that are only valid quoted keep their case and stay quoted, so the output parses back.
+        let c = Parser::new("SELECT [Order Id], [Select] FROM [Key];").parse().unwrap();
+        assert_eq!(c.normalized_sql(), "SELECT [Order Id], [Select] FROM [Key]");
+        assert_eq!(Parser::new(&format!("{};", c.normalized_sql())).parse().unwrap(), c);
+    }
+
+    // Test ORDER BY may mix ordinals and expressions, and validate checks the ordinals' range.
+    #[test]
+    fn test_sql_parser_validate_mixed_order_by() {
+        let statement = Parser::new("SELECT id, name FROM users ORDER BY 1, name DESC;").parse().unwrap();
+        let Statement::Select { orderby, .. } = &statement else {
+            panic!("expected SELECT, got {:?}", statement);
+        };
+        assert_eq!(orderby[0].ordinal(), Some(1));
+        assert_eq!(
+            orderby[1],
+            OrderByItem { expr: Expression::Identifier("name".to_string()), order: Some(SortOrder::Desc) }
+        );
+        assert_eq!(statement.validate(), Ok(()));
+
+        // Out of range is a validation error, not a parse error.
+        let statement = Parser::new("SELECT id, name FROM users ORDER BY name, 5;").parse().unwrap();
+        assert_eq!(statement.validate(), Err(ValidationError::OrdinalOutOfRange { ordinal: 5, column_count: 2 }));
+    }
+
+    // Test the serde feature: a token stream round-trips through JSON.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tokenizer_serde_json() {
+        let tokens = Tokenizer::new("SELECT name FROM users WHERE age >= $1;").tokenize().unwrap();
+        let json = serde_json::to_string(&tokens).unwrap();
+        assert!(json.starts_with("[{\"Keyword\":\"Select\"}"), "{}", json);
+        let back: Vec<Token> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, tokens);
+    }
+
+    // Test compile_pattern splits a LIKE pattern into literal, % and _ segments.
+    #[test]
+    fn test_pratt_parser_like_compile_pattern() {
+        let expr = Parser::new("name LIKE 'a%b_c'").parse_expression_only().unwrap();
+        assert_eq!(
+            expr.compile_pattern(),
+            Some(LikePattern {
+                segments: vec![
+                    LikeSegment::Literal("a".to_string()),
+                    LikeSegment::AnyString,
+                    LikeSegment::Literal("b".to_string()),
+                    LikeSegment::AnyChar,
+                    LikeSegment::Literal("c".to_string()),
+                ],
+            })
+        );
+        // The raw pattern stays on the expression.
+        assert!(matches!(&expr, Expression::Like { pattern, .. } if **pattern == Expression::String("a%b_c".to_string())));
+        // An escaped wildcard is literal text.
+        let expr = Parser::new("code LIKE '100!%%' ESCAPE '!'").parse_expression_only().unwrap();
+        assert_eq!(
+            expr.compile_pattern().unwrap().segments,
+            vec![LikeSegment::Literal("100%".to_string()), LikeSegment::AnyString]
+        );
+        assert_eq!(Parser::new("name LIKE ?").parse_expression_only().unwrap().compile_pattern(), None);
+    }
+
+    // Test parse_table_columns returns a CREATE TABLE's name and columns and rejects other statements.
+    #[test]
+    fn test_sql_parser_parse_table_columns() {
+        let (name, columns) = parse_table_columns("CREATE TABLE users (id INT PRIMARY KEY, name VARCHAR(50) NOT NULL);").unwrap();
+        assert_eq!(name, "users");
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].column_name, "id");
+        assert_eq!(columns[0].constraints, vec![Constraint::PrimaryKey]);
+        assert_eq!(columns[1].column_type, DBType::Varchar(50));
+        assert!(matches!(
+            parse_table_columns("SELECT * FROM users;"),
+            Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::Select)))
+        ));
+        assert!(matches!(
+            parse_table_columns("CREATE TABLE copy AS SELECT * FROM users;"),
+            Err(ParseError::NotCreateTable)
+        ));
+    }
+
+    // Test INSERT ... ON CONFLICT DO NOTHING with and without a conflict target.
+    #[test]
+    fn test_sql_parser_insert_on_conflict_do_nothing() {
+        let input = "INSERT INTO users (id, name) VALUES (1, 'a') ON CONFLICT (id) DO NOTHING;";
+        let statement = Parser::new(input).parse().unwrap();
+        assert!(matches!(
+            &statement,
+            Statement::Insert { on_conflict: Some(OnConflict { target, action: ConflictAction::DoNothing }), .. }
+                if *target == vec!["id".to_string()]
+        ));
+        assert_eq!(statement.to_string(), "INSERT INTO users (id, name) VALUES (1, \"a\") ON CONFLICT (id) DO NOTHING");
+
+        let statement = Parser::new("INSERT INTO t VALUES (1) ON CONFLICT DO NOTHING RETURNING *;").parse().unwrap();
+        assert_eq!(statement.to_string(), "INSERT INTO t VALUES (1) ON CONFLICT DO NOTHING RETURNING *");
+    }
+
+    // Test INSERT ... ON CONFLICT DO UPDATE SET and the MySQL ON DUPLICATE KEY UPDATE form.
+    #[test]
+    fn test_sql_parser_insert_on_conflict_do_update() {
+        let input = "INSERT INTO counters (id, hits) VALUES (1, 1) ON CONFLICT (id) DO UPDATE SET hits = hits + 1;";
+        let statement = Parser::new(input).parse().unwrap();
+        let expected = OnConflict {
+            target: vec!["id".to_string()],
+            action: ConflictAction::DoUpdate(vec![Assignment {
+                column: "hits".to_string(),
+                value: Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("hits".to_string())),
+                    operator: BinaryOperator::Plus,
+                    right_operand: Box::new(Expression::Number(1)),
+                },
+            }]),
+        };
+        assert!(matches!(&statement, Statement::Insert { on_conflict: Some(on_conflict), .. } if *on_conflict == expected));
+        assert_eq!(
+            statement.to_string(),
+            "INSERT INTO counters (id, hits) VALUES (1, 1) ON CONFLICT (id) DO UPDATE SET hits = (hits + 1)"
+        );
+
+        let input = "INSERT INTO counters (id, hits) VALUES (1, ?) ON DUPLICATE KEY UPDATE hits = ?;";
+        let statement = Parser::new(input).parse().unwrap();
+        assert!(matches!(
+            &statement,
+            Statement::Insert { on_conflict: Some(OnConflict { action: ConflictAction::DuplicateKeyUpdate(_), .. }), .. }
+        ));
+        assert_eq!(statement.placeholders().len(), 2);
+        assert_eq!(statement.to_string(), "INSERT INTO counters (id, hits) VALUES (1, ?) ON DUPLICATE KEY UPDATE hits = ?");
+
+        assert!(Parser::new("INSERT INTO t VALUES (1) ON CONFLICT (id) UPDATE SET a = 1;").parse().is_err());
+    }
+
+    // Test to_sql and Display on a 5000-deep left-leaning a + a + ... chain without overflowing the stack.
+    #[test]
+    fn test_pratt_parser_to_sql_deep_expression() {
+        let mut expr = Expression::Identifier("a".to_string());
+        for _ in 0..5000 {
+            expr = Expression::BinaryOperation {
+                left_operand: Box::new(expr),
+                operator: BinaryOperator::Plus,
+                right_operand: Box::new(Expression::Identifier("a".to_string())),
+            };
+        }
+        let sql = expr.to_sql();
+        assert_eq!(sql, vec!["a"; 5001].join(" + "));
+        // Display shares the same iterative renderer, in its fully parenthesized style.
+        let display = expr.to_string();
+        assert!(display.starts_with(&"(".repeat(5000)));
+        assert!(display.ends_with(" + a)"));
+        assert_eq!(display.len(), sql.len() + 2 * 5000);
+
+        // Mixed precedence and unary operators still get the same parentheses as before.
+        let statement = Parser::new("SELECT -(a + b) * c - (d - e), NOT (a OR b) AND c FROM t;").parse().unwrap();
+        let Statement::Select { columns, .. } = statement else { panic!("expected SELECT") };
+        assert_eq!(columns[0].to_sql(), "-(a + b) * c - (d - e)");
+        assert_eq!(columns[1].to_sql(), "NOT (a OR b) AND c");
+    }
+
+    // Test FILTER (WHERE ...) on aggregate calls.
+    #[test]
+    fn test_pratt_parser_function_filter_clause() {
+        let statement = Parser::new("SELECT SUM(x) FILTER (WHERE x > 0) FROM t;").parse().unwrap();
+        let Statement::Select { columns, .. } = &statement else { panic!("expected SELECT") };
+        assert_eq!(
+            columns[0],
+            Expression::FunctionCall {
+                name: "SUM".to_string(),
+                distinct: false,
+                args: vec![Expression::Identifier("x".to_string())],
+                filter: Some(Box::new(Expression::BinaryOperation {
+                    left_operand: Box::new(Expression::Identifier("x".to_string())),
+                    operator: BinaryOperator::GreaterThan,
+                    right_operand: Box::new(Expression::Number(0)),
+                })),
+            }
+        );
+        assert_eq!(columns[0].to_string(), "SUM(x) FILTER (WHERE (x > 0))");
+        assert_eq!(columns[0].to_sql(), "SUM(x) FILTER (WHERE x > 0)");
+
+        // FILTER comes before OVER, and its condition takes part in placeholders.
+        let statement = Parser::new("SELECT COUNT(*) FILTER (WHERE active = ?) OVER (PARTITION BY team) FROM t;").parse().unwrap();
+        assert_eq!(statement.placeholders().len(), 1);
+        assert!(Parser::new("SELECT SUM(x) FILTER (x > 0) FROM t;").parse().is_err());
+
+        // FILTER is only for aggregates.
+        assert!(matches!(
+            Parser::new("SELECT UPPER(x) FILTER (WHERE x > 0) FROM t;").parse(),
+            Err(ParseError::FilterOnNonAggregate(name)) if name == "UPPER"
+        ));
+    }
+
+    // Test filter still works as a column name and alias when no ( follows it.
+    #[test]
+    fn test_sql_parser_filter_as_identifier() {
+        let statement = Parser::new("SELECT filter FROM t WHERE filter > 1;").parse().unwrap();
+        assert_eq!(statement.to_string(), "SELECT filter FROM t WHERE (filter > 1)");
+        let statement = Parser::new("SELECT a AS filter FROM t;").parse().unwrap();
+        assert_eq!(statement.to_string(), "SELECT a AS filter FROM t");
+        assert!(Parser::new("CREATE TABLE t (filter INT);").parse().is_ok());
+
+        let options = ParserOptions { allow_implicit_alias: true, ..ParserOptions::default() };
+        let statement = Parser::with_options("SELECT count(*) filter FROM t;", options).parse().unwrap();
+        assert_eq!(statement.to_string(), "SELECT count(*) AS filter FROM t");
+    }
+
+    // Test SELECT - -1 round-trips through to_sql and format with and without fold_unary_minus.
+    #[test]
+    fn test_pratt_parser_stacked_minus_round_trip() {
+        for fold_unary_minus in [false, true] {
+            let options = ParserOptions { fold_unary_minus, ..ParserOptions::default() };
+            let statement = Parser::with_options("SELECT - -1 FROM t;", options.clone()).parse().unwrap();
+            let sql = statement.format(&FormatOptions::default());
+            assert_eq!(sql, "SELECT - -1 FROM t");
+            let reparsed = Parser::with_options(&format!("{};", sql), options).parse().unwrap();
+            assert_eq!(reparsed, statement);
+        }
+        // A negative literal operand gets the space too; a tighter operator parenthesizes it instead.
+        let expr = Expression::UnaryOperation {
+            operator: UnaryOperator::Minus,
+            operand: Box::new(Expression::SignedNumber(-1)),
+        };
+        assert_eq!(expr.to_sql(), "- -1");
+        assert_eq!(expr.to_string(), "- -1");
+        let expr = Expression::UnaryOperation {
+            operator: UnaryOperator::Minus,
+            operand: Box::new(Expression::Cast { expr: Box::new(Expression::SignedNumber(-1)), data_type: DBType::Int }),
+        };
+        assert_eq!(expr.to_sql(), "-(-1)::INT");
+    }
+
+    // Test the input the ast_json example rejects: parse_script fails on a tokenizer or parse error anywhere in the script.
+    #[test]
+    fn test_sql_parser_script_errors() {
+        assert!(matches!(
+            parse_script("SELECT name FROM users; SELECT 'open FROM t;"),
+            Err(ParseError::Tokenizer(TokenizerError::UnterminatedString))
+        ));
+        assert!(matches!(
+            parse_script("SELECT name FROM users; SELECT FROM t;"),
+            Err(ParseError::UnexpectedToken(Token::Keyword(Keyword::From)))
+        ));
+        // A valid script parses to one statement per semicolon, which the example prints.
+        let statements = parse_script("SELECT name FROM users; DELETE FROM t;").unwrap();
+        assert!(matches!(statements[0].node, Statement::Select { .. }));
+        assert!(matches!(statements[1].node, Statement::Delete { .. }));
+    }
+
+    // Test a script with Windows (\r\n) line endings tokenizes and parses like one with \n.
+    #[test]
+    fn test_tokenizer_crlf_line_endings() {
+        let crlf = "SELECT name\r\nFROM users; -- first\r\nDELETE FROM t\r\nWHERE id = 1;\r\n";
+        let lf = crlf.replace("\r\n", "\n");
+        assert_eq!(Tokenizer::new(crlf).tokenize(), Tokenizer::new(&lf).tokenize());
+        let statements = parse_script(crlf).unwrap();
+        assert_eq!(statements.len(), 2);
+        assert!(matches!(statements[1].node, Statement::Delete { .. }));
+    }
+
+    // Test collation names with quotes and backslashes round-trip, and a second COLLATE on a column is rejected.
+    #[test]
+    fn test_sql_parser_collate_escaping_and_duplicates() {
+        for input in [
+            "CREATE TABLE t (name VARCHAR(10) COLLATE \"a\"\"b\\\\c\");",
+            "SELECT name FROM t ORDER BY name COLLATE 'a\"b\\\\c';",
+        ] {
+            let statement = Parser::new(input).parse().unwrap();
+            let sql = format!("{};", statement);
+            assert!(sql.contains("COLLATE \"a\"\"b\\\\c\""), "{}", sql);
+            assert_eq!(Parser::new(&sql).parse().unwrap(), statement);
+        }
+        match Parser::new("CREATE TABLE t (name VARCHAR(10) COLLATE a NOT NULL COLLATE b);").parse() {
+            Err(ParseError::DuplicateCollation(column)) => assert_eq!(column, "name"),
+            other => panic!("expected DuplicateCollation, got {:?}", other),
+        }
     }
-}
\ No newline at end of file
+}
@@ -8,8 +8,10 @@ use std::fmt::{Debug, Display, Formatter};
 
 // Define Token enum for all possible token types.
 #[derive(PartialEq, Clone, Debug)]
-pub(crate) enum Token {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Token {
     Keyword(Keyword), // SQL keywords (e.g., SELECT).
+    CustomKeyword(String), // Word listed in TokenizerOptions::custom_keywords, as configured; the parser reads it as an identifier.
     Identifier(String), // User-defined names (e.g., table names).
     String(String), // String literals (e.g., "Voldemort").
     Number(u64), // Numeric literals (e.g., 42).
@@ -24,16 +26,50 @@ pub(crate) enum Token {
     NotEqual, // !=.
     Star, // * (for SELECT * and multiplication).
     Divide, // /.
+    Caret, // ^ (exponentiation).
     Minus, // -.
     Plus, // +.
     Comma, // ,.
+    Dot, // . between the parts of a qualified name.
+    DoubleColon, // :: (PostgreSQL cast shorthand, e.g., age::INT).
     Semicolon, // ;.
+    Question, // ? (anonymous bind parameter).
+    PositionalParam(u32), // $1, $2, ... (positional bind parameter).
+    NamedParam(String), // :name or @name (named bind parameter, stored with its prefix).
+    Hint(String), // Optimizer hint comment /*+ ... */, with the inner text trimmed.
     Eof, // End of input.
 }
 
+// Define Span struct for the byte range a token (or larger construct) covers in the input.
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    pub start: usize, // Byte offset of the first character.
+    pub end: usize, // Byte offset just past the last character.
+}
+
+impl Span {
+    // Smallest span covering both spans (e.g., a statement's first keyword through its semicolon).
+    pub fn merge(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+// Define Spanned struct for a parsed node together with the source range it came from.
+#[derive(PartialEq, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Spanned<T> {
+    pub node: T, // The parsed node.
+    pub span: Span, // Source range covering the whole node.
+}
+
 // Define Keyword enum for SQL reserved words.
 #[derive(PartialEq, Clone, Debug)]
-pub(crate) enum Keyword {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Keyword {
     Select, // SELECT for queries.
     Create, // CREATE for table creation.
     Table, // TABLE for CREATE TABLE.
@@ -55,6 +91,105 @@ pub(crate) enum Keyword {
     Bool, // BOOL for column type.
     Varchar, // VARCHAR for column type.
     Null, // NULL for constraints.
+    Default, // DEFAULT for column default values.
+    Collate, // COLLATE for collations.
+    AutoIncrement, // AUTO_INCREMENT / AUTOINCREMENT for constraints.
+    In, // IN for value-list membership.
+    Like, // LIKE for pattern matching.
+    Between, // BETWEEN for range checks.
+    Group, // GROUP for GROUP BY.
+    Having, // HAVING for group filters.
+    Limit, // LIMIT for row limits.
+    Offset, // OFFSET for skipping rows.
+    Distinct, // DISTINCT for de-duplicated aggregate arguments.
+    If, // IF for IF NOT EXISTS.
+    Exists, // EXISTS for IF NOT EXISTS.
+    Date, // DATE column type and typed literal.
+    Timestamp, // TIMESTAMP column type and typed literal.
+    With, // WITH for common table expressions.
+    As, // AS for naming a common table expression.
+    Rows, // ROWS in OFFSET ... ROWS / FETCH ... ROWS ONLY.
+    Fetch, // FETCH for SQL-standard row limits.
+    Next, // NEXT in FETCH NEXT ... ROWS ONLY.
+    Only, // ONLY in FETCH NEXT ... ROWS ONLY.
+    Update, // UPDATE for modifying rows.
+    Set, // SET for UPDATE assignments.
+    All, // ALL for quantified comparisons.
+    Any, // ANY for quantified comparisons.
+    Some, // SOME for quantified comparisons (same as ANY).
+    Insert, // INSERT for adding rows.
+    Into, // INTO for INSERT INTO.
+    Values, // VALUES for INSERT row tuples.
+    Delete, // DELETE for removing rows.
+    Returning, // RETURNING for rows reported back by INSERT/UPDATE/DELETE.
+    Escape, // ESCAPE for LIKE escape characters.
+    Union, // UNION for combining query results.
+    Comment, // COMMENT for column comments (MySQL).
+    Is, // IS for IS [NOT] NULL / TRUE / FALSE / UNKNOWN.
+    Unknown, // UNKNOWN in IS [NOT] UNKNOWN.
+    CurrentDate, // CURRENT_DATE (today, no parentheses).
+    CurrentTimestamp, // CURRENT_TIMESTAMP (now, no parentheses).
+    References, // REFERENCES for foreign key constraints.
+    On, // ON in ON DELETE / ON UPDATE.
+    Cascade, // CASCADE referential action.
+    Restrict, // RESTRICT referential action.
+    Rollup, // ROLLUP in GROUP BY.
+    Cube, // CUBE in GROUP BY.
+    Grouping, // GROUPING in GROUP BY GROUPING SETS.
+    Sets, // SETS in GROUP BY GROUPING SETS.
+    Over, // OVER for window functions.
+    Partition, // PARTITION in OVER (PARTITION BY ...).
+    Explain, // EXPLAIN for query plans.
+    Analyze, // ANALYZE in EXPLAIN ANALYZE.
+    Describe, // DESCRIBE for table structure.
+    Generated, // GENERATED in GENERATED ALWAYS AS (expr).
+    Always, // ALWAYS in GENERATED ALWAYS AS (expr).
+    Stored, // STORED generated column (computed on write).
+    Virtual, // VIRTUAL generated column (computed on read).
+    Conflict, // CONFLICT in INSERT ... ON CONFLICT.
+    Do, // DO in ON CONFLICT ... DO NOTHING/UPDATE.
+    Nothing, // NOTHING in ON CONFLICT ... DO NOTHING.
+    Duplicate, // DUPLICATE in INSERT ... ON DUPLICATE KEY UPDATE.
+    Filter, // FILTER (WHERE ...) on aggregate calls.
+}
+
+impl Keyword {
+    // Reserved keywords always act as keywords. The rest (type names, KEY, COMMENT, UNKNOWN, the
+    // referential actions, the GROUP BY ROLLUP/CUBE/GROUPING SETS words, OVER/PARTITION, the words of
+    // FETCH NEXT ... ROWS ONLY, those of GENERATED ALWAYS AS ... STORED/VIRTUAL, CONFLICT/NOTHING/
+    // DUPLICATE from upserts, and FILTER) may be used as identifiers when the parser allows it.
+    pub fn is_reserved(&self) -> bool {
+        !matches!(
+            self,
+            Keyword::Key
+                | Keyword::Int
+                | Keyword::Bool
+                | Keyword::Varchar
+                | Keyword::Date
+                | Keyword::Timestamp
+                | Keyword::Rows
+                | Keyword::Next
+                | Keyword::Only
+                | Keyword::Comment
+                | Keyword::Unknown
+                | Keyword::Cascade
+                | Keyword::Restrict
+                | Keyword::Rollup
+                | Keyword::Cube
+                | Keyword::Grouping
+                | Keyword::Sets
+                | Keyword::Over
+                | Keyword::Partition
+                | Keyword::Generated
+                | Keyword::Always
+                | Keyword::Stored
+                | Keyword::Virtual
+                | Keyword::Conflict
+                | Keyword::Nothing
+                | Keyword::Duplicate
+                | Keyword::Filter
+        )
+    }
 }
 
 // Implement Display for Token to format tokens as strings.
@@ -62,6 +197,7 @@ impl Display for Token {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Token::Keyword(keyword) => write!(f, "{}", keyword),
+            Token::CustomKeyword(word) => write!(f, "{}", word),
             Token::Identifier(iden) => write!(f, "{}", iden),
             Token::String(s) => write!(f, "\"{}\"", s),
             Token::Number(num) => write!(f, "{}", num),
@@ -75,10 +211,17 @@ impl Display for Token {
             Token::NotEqual => write!(f, "!="),
             Token::Star => write!(f, "*"),
             Token::Divide => write!(f, "/"),
+            Token::Caret => write!(f, "^"),
+            Token::Hint(hint) => write!(f, "/*+ {} */", hint),
             Token::Minus => write!(f, "-"),
             Token::Plus => write!(f, "+"),
             Token::Comma => write!(f, ","),
+            Token::Dot => write!(f, "."),
+            Token::DoubleColon => write!(f, "::"),
             Token::Semicolon => write!(f, ";"),
+            Token::Question => write!(f, "?"),
+            Token::PositionalParam(n) => write!(f, "${}", n),
+            Token::NamedParam(name) => write!(f, "{}", name),
             Token::Eof => write!(f, "EOF"),
             Token::Invalid(c) => write!(f, "Invalid({})", c),
         }
@@ -110,6 +253,66 @@ impl Display for Keyword {
             Keyword::Bool => write!(f, "BOOL"),
             Keyword::Varchar => write!(f, "VARCHAR"),
             Keyword::Null => write!(f, "NULL"),
+            Keyword::Default => write!(f, "DEFAULT"),
+            Keyword::Collate => write!(f, "COLLATE"),
+            Keyword::AutoIncrement => write!(f, "AUTO_INCREMENT"),
+            Keyword::In => write!(f, "IN"),
+            Keyword::Like => write!(f, "LIKE"),
+            Keyword::Between => write!(f, "BETWEEN"),
+            Keyword::Group => write!(f, "GROUP"),
+            Keyword::Having => write!(f, "HAVING"),
+            Keyword::Limit => write!(f, "LIMIT"),
+            Keyword::Offset => write!(f, "OFFSET"),
+            Keyword::Distinct => write!(f, "DISTINCT"),
+            Keyword::If => write!(f, "IF"),
+            Keyword::Exists => write!(f, "EXISTS"),
+            Keyword::Date => write!(f, "DATE"),
+            Keyword::Timestamp => write!(f, "TIMESTAMP"),
+            Keyword::With => write!(f, "WITH"),
+            Keyword::As => write!(f, "AS"),
+            Keyword::Rows => write!(f, "ROWS"),
+            Keyword::Fetch => write!(f, "FETCH"),
+            Keyword::Next => write!(f, "NEXT"),
+            Keyword::Only => write!(f, "ONLY"),
+            Keyword::Update => write!(f, "UPDATE"),
+            Keyword::Set => write!(f, "SET"),
+            Keyword::All => write!(f, "ALL"),
+            Keyword::Any => write!(f, "ANY"),
+            Keyword::Some => write!(f, "SOME"),
+            Keyword::Insert => write!(f, "INSERT"),
+            Keyword::Into => write!(f, "INTO"),
+            Keyword::Values => write!(f, "VALUES"),
+            Keyword::Delete => write!(f, "DELETE"),
+            Keyword::Returning => write!(f, "RETURNING"),
+            Keyword::Escape => write!(f, "ESCAPE"),
+            Keyword::Union => write!(f, "UNION"),
+            Keyword::Comment => write!(f, "COMMENT"),
+            Keyword::Is => write!(f, "IS"),
+            Keyword::Unknown => write!(f, "UNKNOWN"),
+            Keyword::CurrentDate => write!(f, "CURRENT_DATE"),
+            Keyword::CurrentTimestamp => write!(f, "CURRENT_TIMESTAMP"),
+            Keyword::References => write!(f, "REFERENCES"),
+            Keyword::On => write!(f, "ON"),
+            Keyword::Cascade => write!(f, "CASCADE"),
+            Keyword::Restrict => write!(f, "RESTRICT"),
+            Keyword::Rollup => write!(f, "ROLLUP"),
+            Keyword::Cube => write!(f, "CUBE"),
+            Keyword::Grouping => write!(f, "GROUPING"),
+            Keyword::Sets => write!(f, "SETS"),
+            Keyword::Over => write!(f, "OVER"),
+            Keyword::Partition => write!(f, "PARTITION"),
+            Keyword::Explain => write!(f, "EXPLAIN"),
+            Keyword::Analyze => write!(f, "ANALYZE"),
+            Keyword::Describe => write!(f, "DESCRIBE"),
+            Keyword::Generated => write!(f, "GENERATED"),
+            Keyword::Always => write!(f, "ALWAYS"),
+            Keyword::Stored => write!(f, "STORED"),
+            Keyword::Virtual => write!(f, "VIRTUAL"),
+            Keyword::Conflict => write!(f, "CONFLICT"),
+            Keyword::Do => write!(f, "DO"),
+            Keyword::Nothing => write!(f, "NOTHING"),
+            Keyword::Duplicate => write!(f, "DUPLICATE"),
+            Keyword::Filter => write!(f, "FILTER"),
         }
     }
 }
\ No newline at end of file
@@ -5,49 +5,153 @@
 // Includes error handling for unterminated strings and unexpected characters (Functionality #16, 2 points).
 
 // Import token definitions and utilities for tokenization.
-use crate::token::{Token, Keyword};
+use crate::token::{Token, Keyword, Span};
 use std::iter::Peekable;
-use std::str::Chars;
+use std::str::CharIndices;
 
 // Define errors for tokenization issues.
 #[derive(Debug, PartialEq)]
 pub enum TokenizerError {
     UnterminatedString, // String missing closing quote.
     UnexpectedChar(char), // Invalid character encountered.
+    UnterminatedComment, // Block comment missing closing */.
+    NumberTooLarge(String), // Integer literal that does not fit in u64.
+    UnterminatedIdentifier, // Bracketed identifier missing closing ].
+    ControlCharacter(u32), // Control character other than the accepted whitespace, by code point (e.g., NUL).
+}
+
+// Implement Display for TokenizerError to show user-friendly error messages.
+impl std::fmt::Display for TokenizerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenizerError::UnterminatedString => write!(f, "Unterminated string literal"),
+            TokenizerError::UnexpectedChar(c) => write!(f, "Unexpected character: {}", c),
+            TokenizerError::UnterminatedComment => write!(f, "Unterminated block comment"),
+            TokenizerError::NumberTooLarge(num) => write!(f, "Number too large: {}", num),
+            TokenizerError::UnterminatedIdentifier => write!(f, "Unterminated bracketed identifier"),
+            TokenizerError::ControlCharacter(code) => write!(f, "Unexpected control character U+{:04X}", code),
+        }
+    }
+}
+
+// A significant token together with the whitespace and comments that precede it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TokenWithTrivia {
+    pub token: Token, // The significant token.
+    pub span: Span, // Source range of the token itself.
+    pub leading_trivia: String, // Exact whitespace/comment text between the previous token and this one.
+}
+
+// Options that relax tokenizing rules; the defaults give the strict behavior.
+#[derive(Debug, Clone, Default)]
+pub struct TokenizerOptions {
+    pub allow_double_equals: bool, // Accept == as a single Equal token (e.g., `a == b`).
+    pub case_sensitive_keywords: bool, // Only uppercase spellings are keywords; `Select` stays an identifier.
+    pub custom_keywords: Vec<String>, // Extra words tokenized as Token::CustomKeyword (e.g., "upsert" for a dialect).
 }
 
 // Tokenizer struct for processing input string.
 pub struct Tokenizer<'a> {
-    input: Peekable<Chars<'a>>, // Iterator to peek and consume characters.
+    input: Peekable<CharIndices<'a>>, // Iterator to peek and consume characters with their byte offsets.
+    source: &'a str, // Original input, used to recover trivia text.
+    len: usize, // Byte length of the input, used as the offset at end of input.
+    tokens: Vec<Token>, // Token buffer, reused across reset() calls.
+    spans: Vec<Span>, // Span buffer, parallel to tokens.
+    options: TokenizerOptions, // Tokenizing options (strict by default).
+    custom_keywords: Vec<(String, String)>, // options.custom_keywords as (lowercased, configured) pairs, lowercased once.
 }
 
 impl<'a> Tokenizer<'a> {
     // Create a new Tokenizer from input string.
     pub fn new(input: &'a str) -> Self {
+        Tokenizer::with_options(input, TokenizerOptions::default())
+    }
+
+    // Create a new Tokenizer with custom tokenizing options.
+    pub fn with_options(input: &'a str, options: TokenizerOptions) -> Self {
+        let custom_keywords = options.custom_keywords.iter().map(|word| (word.to_lowercase(), word.clone())).collect();
         Tokenizer {
-            input: input.chars().peekable(),
+            input: input.char_indices().peekable(),
+            source: input,
+            len: input.len(),
+            tokens: Vec::new(),
+            spans: Vec::new(),
+            options,
+            custom_keywords,
         }
     }
 
+    // Point the tokenizer at a new input, keeping the token buffers' allocations.
+    // Lets a server tokenize many small queries with one Tokenizer.
+    pub fn reset(&mut self, input: &'a str) {
+        self.input = input.char_indices().peekable();
+        self.source = input;
+        self.len = input.len();
+    }
+
     // Get next character and advance iterator.
     fn next_char(&mut self) -> Option<char> {
-        self.input.next()
+        self.input.next().map(|(_, c)| c)
     }
 
     // Peek at next character without advancing.
     fn peek_char(&mut self) -> Option<&char> {
-        self.input.peek()
+        self.input.peek().map(|(_, c)| c)
+    }
+
+    // Byte offset of the next character (or the input length at end of input).
+    fn offset(&mut self) -> usize {
+        self.input.peek().map(|(i, _)| *i).unwrap_or(self.len)
     }
 
     // Tokenize input into a vector of tokens.
     pub fn tokenize(&mut self) -> Result<Vec<Token>, TokenizerError> {
-        let mut tokens = Vec::new();
+        self.scan()?;
+        Ok(self.tokens.drain(..).collect())
+    }
+
+    // Tokenize input into the internal buffer and borrow the result; after reset, this
+    // refills the same buffer instead of allocating a new vector.
+    pub fn tokenize_buffered(&mut self) -> Result<&[Token], TokenizerError> {
+        self.scan()?;
+        Ok(&self.tokens)
+    }
+
+    // Tokenize input, keeping the whitespace and comments in front of each token as leading trivia.
+    // Text after the last token is attached to Eof. Useful for formatters that must not lose comments.
+    pub fn tokenize_with_trivia(&mut self) -> Result<Vec<TokenWithTrivia>, TokenizerError> {
+        let tokens = self.tokenize_with_spans()?;
+        let mut previous_end = 0;
+        let mut result = Vec::with_capacity(tokens.len());
+        for (token, span) in tokens {
+            let leading_trivia = self.source[previous_end..span.start].to_string();
+            previous_end = span.end;
+            result.push(TokenWithTrivia { token, span, leading_trivia });
+        }
+        Ok(result)
+    }
+
+    // Tokenize input into tokens paired with the byte range each one covers in the input.
+    pub fn tokenize_with_spans(&mut self) -> Result<Vec<(Token, Span)>, TokenizerError> {
+        self.scan()?;
+        Ok(self.tokens.drain(..).zip(self.spans.drain(..)).collect())
+    }
+
+    // Clear the buffers and fill them with the tokens of the current input and their spans.
+    fn scan(&mut self) -> Result<(), TokenizerError> {
+        // Work on the buffers locally; they are handed back once the whole input is scanned.
+        let mut tokens = std::mem::take(&mut self.tokens);
+        let mut spans = std::mem::take(&mut self.spans);
+        tokens.clear();
+        spans.clear();
 
         // Process each character in input.
         while let Some(&c) = self.peek_char() {
+            let start = self.offset();
+            let token_count = tokens.len();
             match c {
-                ' ' | '\t' | '\n' => {
-                    self.next_char(); // Skip whitespace.
+                ' ' | '\t' | '\r' | '\n' => {
+                    self.next_char(); // Skip whitespace, including the \r of Windows line endings.
                 }
                 '(' => {
                     self.next_char();
@@ -61,6 +165,10 @@ impl<'a> Tokenizer<'a> {
                     self.next_char();
                     tokens.push(Token::Comma);
                 }
+                '.' => {
+                    self.next_char();
+                    tokens.push(Token::Dot);
+                }
                 ';' => {
                     self.next_char();
                     tokens.push(Token::Semicolon);
@@ -69,9 +177,22 @@ impl<'a> Tokenizer<'a> {
                     self.next_char();
                     tokens.push(Token::Plus);
                 }
+                '^' => {
+                    self.next_char();
+                    tokens.push(Token::Caret);
+                }
                 '-' => {
                     self.next_char();
-                    tokens.push(Token::Minus);
+                    if self.peek_char() == Some(&'-') {
+                        // Line comment: skip everything up to the end of the line.
+                        while let Some(c) = self.next_char() {
+                            if c == '\n' {
+                                break;
+                            }
+                        }
+                    } else {
+                        tokens.push(Token::Minus);
+                    }
                 }
                 '*' => {
                     self.next_char();
@@ -79,10 +200,38 @@ impl<'a> Tokenizer<'a> {
                 }
                 '/' => {
                     self.next_char();
-                    tokens.push(Token::Divide);
+                    if self.peek_char() == Some(&'*') {
+                        // Block comment: skip everything up to the closing */.
+                        // A comment starting with /*+ is an optimizer hint and is kept as Token::Hint.
+                        self.next_char();
+                        let is_hint = self.peek_char() == Some(&'+');
+                        let mut text = String::new();
+                        let mut previous = '\0';
+                        loop {
+                            match self.next_char() {
+                                Some('/') if previous == '*' => break,
+                                Some(c) => {
+                                    text.push(c);
+                                    previous = c;
+                                }
+                                None => return Err(TokenizerError::UnterminatedComment),
+                            }
+                        }
+                        if is_hint {
+                            // Drop the leading + and the trailing * of the delimiters.
+                            let inner = &text[1..text.len() - 1];
+                            tokens.push(Token::Hint(inner.trim().to_string()));
+                        }
+                    } else {
+                        tokens.push(Token::Divide);
+                    }
                 }
                 '=' => {
                     self.next_char();
+                    // With allow_double_equals, == is another spelling of =.
+                    if self.options.allow_double_equals && self.peek_char() == Some(&'=') {
+                        self.next_char();
+                    }
                     tokens.push(Token::Equal); // For equality comparisons.
                 }
                 '>' => {
@@ -112,12 +261,68 @@ impl<'a> Tokenizer<'a> {
                         return Err(TokenizerError::UnexpectedChar('!')); // Error for lone !.
                     }
                 }
+                '?' => {
+                    self.next_char();
+                    tokens.push(Token::Question); // Anonymous bind parameter.
+                }
+                '$' => {
+                    self.next_char();
+                    let mut digits = String::new();
+                    while let Some(&c) = self.peek_char() {
+                        if c.is_ascii_digit() {
+                            digits.push(c);
+                            self.next_char();
+                        } else {
+                            break;
+                        }
+                    }
+                    // A positional parameter needs a number that fits in u32 (e.g., $1).
+                    match digits.parse::<u32>() {
+                        Ok(position) => tokens.push(Token::PositionalParam(position)),
+                        Err(_) => return Err(TokenizerError::UnexpectedChar('$')),
+                    }
+                }
+                ':' | '@' => {
+                    self.next_char();
+                    // :: is the cast operator; a single : still starts a named parameter.
+                    if c == ':' && self.peek_char() == Some(&':') {
+                        self.next_char();
+                        tokens.push(Token::DoubleColon);
+                    } else {
+                        // A named parameter must start with a letter or underscore right after the prefix.
+                        let mut name = String::from(c);
+                        while let Some(&next) = self.peek_char() {
+                            let valid = if name.len() == 1 {
+                                next.is_alphabetic() || next == '_'
+                            } else {
+                                next.is_alphabetic() || next.is_ascii_digit() || next == '_'
+                            };
+                            if !valid {
+                                break;
+                            }
+                            name.push(next);
+                            self.next_char();
+                        }
+                        if name.len() == 1 {
+                            return Err(TokenizerError::UnexpectedChar(c)); // Error for lone : or @.
+                        }
+                        tokens.push(Token::NamedParam(name));
+                    }
+                }
                 '"' | '\'' => {
                     let quote = c;
                     self.next_char();
                     let mut string = String::new();
+                    let mut terminated = false;
                     while let Some(c) = self.next_char() {
                         if c == quote {
+                            // A doubled quote ('it''s') stands for one quote character inside the string.
+                            if self.peek_char() == Some(&quote) {
+                                self.next_char();
+                                string.push(quote);
+                                continue;
+                            }
+                            terminated = true;
                             break; // End of string.
                         }
                         if c == '\\' {
@@ -135,28 +340,52 @@ impl<'a> Tokenizer<'a> {
                         }
                         string.push(c);
                     }
-                    if self.peek_char().is_none() && string.is_empty() {
-                        return Err(TokenizerError::UnterminatedString); // Error for empty unterminated string.
+                    if !terminated {
+                        return Err(TokenizerError::UnterminatedString); // Error for missing closing quote.
                     }
                     tokens.push(Token::String(string)); // Store string literal.
                 }
+                '[' => {
+                    // SQL Server delimited identifier: [order id] keeps its exact text and is never a keyword.
+                    self.next_char();
+                    let mut ident = String::new();
+                    let mut terminated = false;
+                    while let Some(c) = self.next_char() {
+                        if c == ']' {
+                            // A doubled bracket ([a]]b]) stands for one ] inside the name.
+                            if self.peek_char() == Some(&']') {
+                                self.next_char();
+                                ident.push(']');
+                                continue;
+                            }
+                            terminated = true;
+                            break;
+                        }
+                        ident.push(c);
+                    }
+                    if !terminated {
+                        return Err(TokenizerError::UnterminatedIdentifier);
+                    }
+                    tokens.push(Token::Identifier(ident));
+                }
                 '0'..='9' => {
                     let mut num = String::new();
                     while let Some(&c) = self.peek_char() {
-                        if c.is_digit(10) {
+                        if c.is_ascii_digit() {
                             num.push(c);
                             self.next_char();
                         } else {
                             break;
                         }
                     }
-                    let number = num.parse::<u64>().unwrap(); // Convert to u64.
+                    // Convert to u64; digits-only input can still overflow.
+                    let number = num.parse::<u64>().map_err(|_| TokenizerError::NumberTooLarge(num))?;
                     tokens.push(Token::Number(number));
                 }
                 'a'..='z' | 'A'..='Z' | '_' => {
                     let mut ident = String::new();
                     while let Some(&c) = self.peek_char() {
-                        if c.is_alphabetic() || c.is_digit(10) || c == '_' {
+                        if c.is_alphabetic() || c.is_ascii_digit() || c == '_' {
                             ident.push(c);
                             self.next_char();
                         } else {
@@ -164,8 +393,11 @@ impl<'a> Tokenizer<'a> {
                         }
                     }
                     let ident_lower = ident.to_lowercase();
+                    let keyword_casing = !self.options.case_sensitive_keywords || !ident.chars().any(char::is_lowercase);
                     // Map identifiers to keywords or keep as identifiers.
                     let token = match ident_lower.as_str() {
+                        // With case_sensitive_keywords, other casings of a keyword stay identifiers.
+                        _ if !keyword_casing => Token::Identifier(ident),
                         "select" => Token::Keyword(Keyword::Select),
                         "from" => Token::Keyword(Keyword::From),
                         "where" => Token::Keyword(Keyword::Where),
@@ -173,31 +405,150 @@ impl<'a> Tokenizer<'a> {
                         "by" => Token::Keyword(Keyword::By),
                         "create" => Token::Keyword(Keyword::Create),
                         "table" => Token::Keyword(Keyword::Table),
-                        "int" => Token::Keyword(Keyword::Int),
+                        "int" | "integer" => Token::Keyword(Keyword::Int), // INTEGER is the long spelling.
                         "varchar" => Token::Keyword(Keyword::Varchar),
-                        "bool" => Token::Keyword(Keyword::Bool),
+                        "bool" | "boolean" => Token::Keyword(Keyword::Bool), // BOOLEAN is the long spelling.
                         "primary" => Token::Keyword(Keyword::Primary),
                         "key" => Token::Keyword(Keyword::Key),
                         "not" => Token::Keyword(Keyword::Not),
                         "null" => Token::Keyword(Keyword::Null),
                         "check" => Token::Keyword(Keyword::Check),
+                        "default" => Token::Keyword(Keyword::Default),
+                        "collate" => Token::Keyword(Keyword::Collate),
+                        "auto_increment" | "autoincrement" => Token::Keyword(Keyword::AutoIncrement),
+                        "in" => Token::Keyword(Keyword::In),
+                        "like" => Token::Keyword(Keyword::Like),
+                        "between" => Token::Keyword(Keyword::Between),
+                        "group" => Token::Keyword(Keyword::Group),
+                        "having" => Token::Keyword(Keyword::Having),
+                        "limit" => Token::Keyword(Keyword::Limit),
+                        "offset" => Token::Keyword(Keyword::Offset),
+                        "distinct" => Token::Keyword(Keyword::Distinct),
+                        "if" => Token::Keyword(Keyword::If),
+                        "exists" => Token::Keyword(Keyword::Exists),
+                        "date" => Token::Keyword(Keyword::Date),
+                        "timestamp" => Token::Keyword(Keyword::Timestamp),
+                        "with" => Token::Keyword(Keyword::With),
+                        "as" => Token::Keyword(Keyword::As),
+                        "rows" => Token::Keyword(Keyword::Rows),
+                        "fetch" => Token::Keyword(Keyword::Fetch),
+                        "next" => Token::Keyword(Keyword::Next),
+                        "only" => Token::Keyword(Keyword::Only),
+                        "update" => Token::Keyword(Keyword::Update),
+                        "set" => Token::Keyword(Keyword::Set),
+                        "all" => Token::Keyword(Keyword::All),
+                        "any" => Token::Keyword(Keyword::Any),
+                        "some" => Token::Keyword(Keyword::Some),
+                        "insert" => Token::Keyword(Keyword::Insert),
+                        "into" => Token::Keyword(Keyword::Into),
+                        "values" => Token::Keyword(Keyword::Values),
+                        "delete" => Token::Keyword(Keyword::Delete),
+                        "returning" => Token::Keyword(Keyword::Returning),
+                        "escape" => Token::Keyword(Keyword::Escape),
+                        "union" => Token::Keyword(Keyword::Union),
+                        "comment" => Token::Keyword(Keyword::Comment),
+                        "is" => Token::Keyword(Keyword::Is),
+                        "unknown" => Token::Keyword(Keyword::Unknown),
+                        "current_date" => Token::Keyword(Keyword::CurrentDate),
+                        "current_timestamp" => Token::Keyword(Keyword::CurrentTimestamp),
+                        "references" => Token::Keyword(Keyword::References),
+                        "on" => Token::Keyword(Keyword::On),
+                        "cascade" => Token::Keyword(Keyword::Cascade),
+                        "restrict" => Token::Keyword(Keyword::Restrict),
+                        "rollup" => Token::Keyword(Keyword::Rollup),
+                        "cube" => Token::Keyword(Keyword::Cube),
+                        "grouping" => Token::Keyword(Keyword::Grouping),
+                        "sets" => Token::Keyword(Keyword::Sets),
+                        "over" => Token::Keyword(Keyword::Over),
+                        "partition" => Token::Keyword(Keyword::Partition),
+                        "explain" => Token::Keyword(Keyword::Explain),
+                        "analyze" => Token::Keyword(Keyword::Analyze),
+                        "describe" => Token::Keyword(Keyword::Describe),
+                        "generated" => Token::Keyword(Keyword::Generated),
+                        "always" => Token::Keyword(Keyword::Always),
+                        "stored" => Token::Keyword(Keyword::Stored),
+                        "virtual" => Token::Keyword(Keyword::Virtual),
+                        "conflict" => Token::Keyword(Keyword::Conflict),
+                        "do" => Token::Keyword(Keyword::Do),
+                        "nothing" => Token::Keyword(Keyword::Nothing),
+                        "duplicate" => Token::Keyword(Keyword::Duplicate),
+                        "filter" => Token::Keyword(Keyword::Filter),
                         "true" => Token::Keyword(Keyword::True),
                         "false" => Token::Keyword(Keyword::False),
                         "and" => Token::Keyword(Keyword::And),
                         "or" => Token::Keyword(Keyword::Or),
                         "asc" => Token::Keyword(Keyword::Asc),
                         "desc" => Token::Keyword(Keyword::Desc),
-                        _ => Token::Identifier(ident),
+                        // Custom keywords cannot shadow built-in ones and follow the same casing rules.
+                        _ => match self.custom_keywords.iter().find(|(lower, _)| *lower == ident_lower) {
+                            Some((_, word)) => Token::CustomKeyword(word.clone()),
+                            None => Token::Identifier(ident),
+                        },
                     };
                     tokens.push(token);
                 }
+                // Report control characters by code point, since printing them is unreadable.
+                _ if c.is_control() => {
+                    self.next_char();
+                    return Err(TokenizerError::ControlCharacter(c as u32));
+                }
                 _ => {
                     self.next_char();
                     return Err(TokenizerError::UnexpectedChar(c)); // Error for invalid chars.
                 }
             }
+            // Each arm pushes at most one token; record the span it covered.
+            if tokens.len() > token_count {
+                spans.push(Span { start, end: self.offset() });
+            }
         }
         tokens.push(Token::Eof); // Mark end of input.
-        Ok(tokens)
+        spans.push(Span { start: self.len, end: self.len });
+        self.tokens = tokens;
+        self.spans = spans;
+        Ok(())
     }
-}
\ No newline at end of file
+}
+
+// Split a script into statements at top-level semicolons, so a ; inside a string or comment does not
+// split. Each statement's source text runs from its first token through its terminating ; (the last one
+// may have none); comments between statements and empty statements are dropped. If the script does not
+// tokenize, it is returned whole so the parser can report the error.
+pub fn split_statements(input: &str) -> Vec<String> {
+    split_statements_with_options(input, TokenizerOptions::default(), &Token::Semicolon)
+}
+
+// Like split_statements, but tokenizing with the given options and ending statements at the given
+// delimiter token; pass ParserOptions::tokenizer and ::delimiter to split as parse_script_with_options parses.
+pub fn split_statements_with_options(input: &str, options: TokenizerOptions, delimiter: &Token) -> Vec<String> {
+    let Ok(tokens) = Tokenizer::with_options(input, options).tokenize_with_spans() else {
+        return vec![input.trim().to_string()];
+    };
+    let mut statements = Vec::new();
+    let mut start = None;
+    let mut last_end = 0;
+    for (token, span) in tokens {
+        // As in the parser, a custom keyword is the identifier it spells.
+        let token = match token {
+            Token::CustomKeyword(word) => Token::Identifier(word),
+            token => token,
+        };
+        match token {
+            _ if token == *delimiter => {
+                if let Some(start) = start.take() {
+                    statements.push(input[start..span.end].to_string());
+                }
+            }
+            Token::Eof => {
+                if let Some(start) = start.take() {
+                    statements.push(input[start..last_end].to_string());
+                }
+            }
+            _ => {
+                start.get_or_insert(span.start);
+                last_end = span.end;
+            }
+        }
+    }
+    statements
+}